@@ -0,0 +1,22 @@
+use crate::audit;
+use crate::models::{AuditQuery, Claims};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+
+/// Lists the audit log across offices, most recent first, optionally filtered by office, actor
+/// and time range.
+pub async fn audit_get(
+    filter: AuditQuery,
+    claims: Claims,
+    _v: u8,
+    range: u16,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(&claims, policy::AUDIT_GET, &Resource::none())?;
+
+    let entries = audit::query(&filter, range).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&entries),
+        extra: None::<Empty>,
+    }))
+}
@@ -1,16 +1,25 @@
+use crate::audit::{self, Changes};
 use crate::fault::Fault;
-use crate::models::{AuthEmail, Claims, RoleFlags, User};
-use crate::util::{self, DataRequest, DataResponse, Empty};
-use crate::{AUTH_EMAIL_COLLECTION, USER_COLLECTION};
+use crate::models::{AuthEmail, Claims, RefreshTokenFamily, User};
+use crate::password;
+use crate::policy::{self, Resource};
+use crate::throttle::{self, Subject};
+use crate::token;
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, REFRESH_TOKEN_COLLECTION, USER_COLLECTION};
 use cosmos_utils::get;
 use cosmos_utils::upsert;
 use warp::reject;
 
-/// This is the endpoint for changing the password
+/// This is the endpoint for changing the password. A signed in user changes their own password with
+/// the old one, global personnel admins set it without. Changing the password signs out every
+/// session of the user.
 pub async fn change_password(
     user_id: String,
     r: DataRequest<String, String>,
-    claims: Option<Claims>,
+    claims: Claims,
+    request_id: String,
+    ip: String,
     _v: u8,
 ) -> Result<impl warp::Reply, warp::Rejection> {
     let new_password;
@@ -20,6 +29,7 @@ pub async fn change_password(
         return Err(reject::custom(Fault::NoData));
     }
     let old_password = r.extra;
+    let is_own_change = old_password.is_some();
     let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
     // Normalise email.
     let email = user.email.to_lowercase();
@@ -28,31 +38,34 @@ pub async fn change_password(
         get(AUTH_EMAIL_COLLECTION, [&email], &email).await?;
 
     if let Some(old_password) = old_password {
-        // Check old password.
-        if !util::verify_hash(&auth_email.passhash, old_password.as_bytes()) {
-            return Err(reject::custom(Fault::WrongPassword));
-        }
-    } else if let Some(claims) = claims {
-        // If the calling user is an office personnel admin for some office they can change the
-        // password
-        let mut ok = false;
-        for e in &claims.rol {
-            if let Some(_) = &e.sub {
-                if e.flg.contains(RoleFlags::OFFICE_PERSONNEL_ADMIN) {
-                    ok = true;
-                    break;
-                }
-            }
+        // Only the user themselves, signed in, can change their password with the old one.
+        policy::authorize(
+            &claims,
+            policy::USER_CREDENTIALS_PUT,
+            &Resource::user(&user),
+        )?;
+        if claims.sub != user.id {
+            return Err(reject::custom(Fault::Forbidden(String::from(
+                "Only the user can change their own password",
+            ))));
         }
-        if !ok {
-            return Err(reject::custom(Fault::NoExtra));
+        // Check old password, failures count towards the same lockout as signing in.
+        throttle::check(Subject::Ip(&ip)).await?;
+        throttle::check(Subject::Email(&email)).await?;
+        if !password::verify(&auth_email.passhash, &old_password)? {
+            throttle::record_failure(Subject::Ip(&ip)).await?;
+            throttle::record_failure(Subject::Email(&email)).await?;
+            return Err(reject::custom(Fault::WrongPassword));
         }
     } else {
-        return Err(reject::custom(Fault::NoExtra));
+        // Global personnel admins can set the password without knowing the old one.
+        policy::authorize(&claims, policy::USER_PASSWORD_RESET, &Resource::user(&user))?;
     }
 
+    password::check(&new_password, &user)?;
+
     // Set new password.
-    auth_email.passhash = util::hash(new_password.as_bytes()); // Calculate pass hash.
+    auth_email.passhash = password::hash(&new_password)?;
 
     upsert(
         AUTH_EMAIL_COLLECTION,
@@ -62,8 +75,36 @@ pub async fn change_password(
     )
     .await?;
 
+    if !is_own_change {
+        audit::record(
+            &request_id,
+            &claims,
+            policy::USER_PASSWORD_RESET,
+            &user.id,
+            &Resource::user(&user),
+            Changes::default(),
+        )
+        .await;
+    }
+
+    // The session the user changed their password from is replaced after signing out every
+    // session, and keeps its second factor.
+    let family = match (is_own_change, &claims.fam) {
+        (true, Some(family_id)) => {
+            let (family, _): (RefreshTokenFamily, _) =
+                get(REFRESH_TOKEN_COLLECTION, [&user.id], family_id).await?;
+            Some(family)
+        }
+        _ => None,
+    };
+    let user = token::revoke_all(&user_id).await?;
+    let tokens = match family {
+        Some(family) => Some(token::issue(&user, family.two_factor, &ip).await?),
+        None => None,
+    };
+
     Ok(warp::reply::json(&DataResponse {
-        data: None::<Empty>,
+        data: tokens,
         extra: None::<Empty>,
     }))
 }
@@ -0,0 +1,86 @@
+use crate::fault::Fault;
+use crate::models::{AuthEmail, TokenPurpose, User};
+use crate::token;
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, USER_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{get, CosmosErrorKind, CosmosSaga};
+use warp::reject;
+
+/// Changes the user's email given the token from the link sent to the new address. The auth email
+/// is keyed by the address, so it is moved to a new document.
+pub async fn confirm_email_change(
+    r: DataRequest<String, Empty>,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let change_token = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+
+    let claims = token::decode_purpose_token(&change_token, TokenPurpose::ChangeEmail)?;
+    let new_email = claims
+        .eml
+        .ok_or_else(|| reject::custom(Fault::Unauthorized))?;
+
+    let (user, user_etag): (User, _) = get(USER_COLLECTION, [&claims.sub], &claims.sub).await?;
+    if user.pending_email.as_ref() != Some(&new_email) {
+        return Err(reject::custom(Fault::IllegalState(String::from(
+            "The email change is no longer pending",
+        ))));
+    }
+    let old_email = user.email.to_lowercase();
+    let (old_auth_email, old_auth_email_etag): (AuthEmail, _) =
+        get(AUTH_EMAIL_COLLECTION, [&old_email], &old_email).await?;
+
+    // NOTE: Documents in different partitions can not be changed in one transaction, the saga
+    // undoes the earlier steps if a step fails.
+    let new_auth_email = AuthEmail {
+        id: new_email.clone(),
+        ..old_auth_email
+    };
+    let mut new_user = user.clone();
+    new_user.email = new_email.clone();
+    new_user.pending_email = None;
+    // Opening the link has proven that the user owns the address.
+    new_user.email_verified_at = Some(Utc::now());
+    new_user.modified = Utc::now();
+
+    let mut saga = CosmosSaga::new();
+    saga.insert(
+        AUTH_EMAIL_COLLECTION,
+        [&new_email],
+        &new_auth_email,
+        &new_email,
+        None,
+    )
+    .await
+    .map_err(|e| match e.kind {
+        CosmosErrorKind::Conflict => reject::custom(Fault::Duplicate(String::from(
+            "The email is already in use",
+        ))),
+        _ => e.into(),
+    })?;
+    saga.upsert(
+        USER_COLLECTION,
+        [&user.id],
+        &new_user,
+        &user.id,
+        Some(&user_etag),
+    )
+    .await?;
+    saga.delete::<AuthEmail, _, _, _>(
+        AUTH_EMAIL_COLLECTION,
+        [&old_email],
+        &old_email,
+        Some(old_auth_email_etag),
+    )
+    .await?;
+    saga.finalize().await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
@@ -1,8 +1,9 @@
 use crate::{
     fault::Fault,
-    models::{Payment, Subscription},
-    util::{log_critical, DataRequest, DataResponse, Empty},
-    APPLICATION_INSIGHTS_TELEMETRY_CLIENT, CRON_SECRET, IN_APP_PURCHASES_APPLE_BUNDLE_ID,
+    models::{Claims, Payment, Subscription},
+    policy::{self, Resource},
+    util::{log_critical, DataResponse, Empty},
+    APPLICATION_INSIGHTS_TELEMETRY_CLIENT, IN_APP_PURCHASES_APPLE_BUNDLE_ID,
     IN_APP_PURCHASES_APPLE_ISSUER, IN_APP_PURCHASES_APPLE_KEY, IN_APP_PURCHASES_APPLE_KEY_ID,
     IN_APP_PURCHASES_APPLE_PASSWORD, IN_APP_PURCHASES_GOOGLE_KEY,
     IN_APP_PURCHASES_GOOGLE_SERVICE_ACCOUNT, PRODUCTION_ENVIRONMENT, SUBSCRIPTION_COLLECTION,
@@ -12,20 +13,8 @@ use chrono::{TimeZone, Utc};
 use cosmos_utils::{query_crosspartition_etag, upsert};
 use warp::reject;
 
-pub async fn cron(
-    r: DataRequest<Empty, String>,
-    _v: u8,
-) -> Result<impl warp::Reply, warp::Rejection> {
-    match r.extra {
-        Some(extra) => {
-            if &extra != &*CRON_SECRET {
-                return Err(reject::custom(Fault::Unauthorized));
-            }
-        }
-        None => {
-            return Err(reject::custom(Fault::NoExtra));
-        }
-    };
+pub async fn cron(claims: Claims, _v: u8) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(&claims, policy::SCHEDULED_JOBS_RUN, &Resource::none())?;
 
     let gateway = match in_app_purchases::Gateway::new(
         IN_APP_PURCHASES_APPLE_BUNDLE_ID.to_string(),
@@ -1,28 +1,27 @@
-use crate::fault::Fault;
-use crate::models::{Claims, Episode, RoleFlags};
-use crate::util::{has_role, DataResponse, Empty};
+use crate::audit;
+use crate::models::{Claims, Episode};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
 use crate::{EPISODE_COLLECTION, EPISODE_IMAGE_STORAGE_CONTAINER};
 use chrono::Utc;
 use cosmos_utils::{get, upload_blob, upsert};
 use warp::filters::multipart::FormData;
-use warp::reject;
 
 impl Episode {
     pub async fn image(
         office_id: String,
         episode_id: String,
         claims: Claims,
+        request_id: String,
         _v: u8,
         f: FormData,
     ) -> Result<impl warp::Reply, warp::Rejection> {
-        if !has_role(Some(&office_id), &claims, RoleFlags::OFFICE_CONTENT_ADMIN) {
-            return Err(reject::custom(Fault::Forbidden(format!(
-                "Caller is not a office content admin",
-            ))));
-        }
+        let resource = Resource::office(&office_id);
+        policy::authorize(&claims, policy::EPISODE_PUT, &resource)?;
 
-        let (mut episode, etag): (Episode, _) =
+        let (old_episode, etag): (Episode, _) =
             get(EPISODE_COLLECTION, [&office_id], &episode_id).await?;
+        let mut episode = old_episode.clone();
 
         let image_id = upload_blob(f, "image", "image", EPISODE_IMAGE_STORAGE_CONTAINER).await?;
         episode.images.push(image_id);
@@ -30,6 +29,16 @@ impl Episode {
 
         upsert(EPISODE_COLLECTION, [&office_id], &episode, Some(&etag)).await?;
 
+        audit::record(
+            &request_id,
+            &claims,
+            policy::EPISODE_PUT,
+            &episode.id,
+            &resource,
+            audit::changes(Some(&old_episode), Some(&episode)),
+        )
+        .await;
+
         // TODO: Delete old image, if any.
         Ok(warp::reply::json(&DataResponse {
             data: Some(episode),
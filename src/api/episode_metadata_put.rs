@@ -1,4 +1,5 @@
 use crate::models::{Claims, Episode, EpisodeMetadata};
+use crate::policy::{self, Resource};
 use crate::util::{DataRequest, Empty};
 use crate::{EPISODE_COLLECTION, EPISODE_METADATA_COLLECTION};
 use cosmos_utils::{modify, modify_async};
@@ -33,14 +34,11 @@ impl EpisodeMetadata {
                 ),
             )));
         }
-        if claims.sub != user_id {
-            return Err(warp::reject::custom(crate::fault::Fault::Forbidden(
-                format!(
-                    "Calling user does not have the privilege, {} != {}",
-                    claims.sub, user_id
-                ),
-            )));
-        }
+        policy::authorize(
+            &claims,
+            policy::EPISODE_METADATA_PUT,
+            &Resource::owner(&user_id),
+        )?;
         let instance = modify_async(
             EPISODE_METADATA_COLLECTION,
             [&user_id],
@@ -1,5 +1,7 @@
-use crate::models::{Claims, Episode, RoleFlags};
-use crate::util::{has_role, DataRequest, Empty};
+use crate::audit;
+use crate::models::{Claims, Episode};
+use crate::policy::{self, Resource};
+use crate::util::{DataRequest, Empty};
 use crate::EPISODE_COLLECTION;
 
 impl Episode {
@@ -7,6 +9,7 @@ impl Episode {
         office_id: String,
         r: DataRequest<Episode, Empty>,
         claims: Claims,
+        request_id: String,
         _v: u8,
     ) -> Result<impl warp::Reply, warp::Rejection> {
         let mut instance;
@@ -23,15 +26,23 @@ impl Episode {
                 ),
             )));
         }
-        if !has_role(None, &claims, RoleFlags::OFFICE_CONTENT_ADMIN) {
-            return Err(warp::reject::custom(crate::fault::Fault::Forbidden(
-                format!("Calling user does not have the privilege.",),
-            )));
-        }
+        let resource = Resource::office(&office_id);
+        policy::authorize(&claims, policy::EPISODE_POST, &resource)?;
         instance.id = uuid::Uuid::new_v4().to_string();
         instance.published = chrono::Utc::now();
         instance.modified = chrono::Utc::now();
         cosmos_utils::insert(EPISODE_COLLECTION, [&instance.office_id], &instance, None).await?;
+
+        audit::record(
+            &request_id,
+            &claims,
+            policy::EPISODE_POST,
+            &instance.id,
+            &resource,
+            audit::changes(None, Some(&instance)),
+        )
+        .await;
+
         Ok(warp::reply::json(&crate::util::DataResponse {
             data: Some(instance),
             extra: None::<crate::util::Empty>,
@@ -1,4 +1,6 @@
-use crate::models::{Claims, Episode, RoleFlags};
+use crate::audit;
+use crate::models::{Claims, Episode};
+use crate::policy::{self, Resource};
 use crate::util::Empty;
 use crate::{EPISODE_COLLECTION, RECORDINGS_STORAGE_CONTAINER};
 use chrono::Utc;
@@ -9,16 +11,15 @@ impl Episode {
         office_id: String,
         episode_id: String,
         claims: Claims,
+        request_id: String,
         _v: u8,
         f: FormData,
     ) -> Result<impl warp::Reply, warp::Rejection> {
-        if !crate::util::has_role(Some(&office_id), &claims, RoleFlags::OFFICE_CONTENT_ADMIN) {
-            return Err(warp::reject::custom(crate::fault::Fault::Forbidden(
-                format!("Insufficient roles, caller does not have privileges for office",),
-            )));
-        }
-        let (mut instance, etag): (Self, _) =
+        let resource = Resource::office(&office_id);
+        policy::authorize(&claims, policy::EPISODE_PUT, &resource)?;
+        let (old_instance, etag): (Self, _) =
             cosmos_utils::get(EPISODE_COLLECTION, [&office_id], &episode_id).await?;
+        let mut instance = old_instance.clone();
         let sound_id =
             cosmos_utils::upload_blob(f, "sound", "audio/", RECORDINGS_STORAGE_CONTAINER).await?;
         instance.sound_file = Some(sound_id);
@@ -26,6 +27,16 @@ impl Episode {
 
         cosmos_utils::upsert(EPISODE_COLLECTION, [&office_id], &instance, Some(&etag)).await?;
 
+        audit::record(
+            &request_id,
+            &claims,
+            policy::EPISODE_PUT,
+            &instance.id,
+            &resource,
+            audit::changes(Some(&old_instance), Some(&instance)),
+        )
+        .await;
+
         // TODO: Delete old sound, if any.
         Ok(warp::reply::json(&crate::util::DataResponse {
             data: Some(instance),
@@ -1,15 +1,22 @@
+use crate::email;
 use crate::fault::Fault;
-use crate::models::{AuthEmail, User};
+use crate::models::{AuthEmail, PasswordReset};
+use crate::throttle::{self, Subject};
 use crate::util::{self, log, DataRequest, DataResponse, Empty};
-use crate::SENDGRID_API_KEY;
-use crate::{AUTH_EMAIL_COLLECTION, USER_COLLECTION};
-use cosmos_utils::get;
-use cosmos_utils::modify;
-use sendgrid::v3::*;
+use crate::{AUTH_EMAIL_COLLECTION, PASSWORD_RESET_COLLECTION};
+use chrono::{Duration, Utc};
+use cosmos_utils::{get, insert, CosmosErrorKind};
 use warp::reject;
 
+/// How long a reset token can be used.
+const RESET_LIFETIME_MINUTES: i64 = 60;
+
+/// Emails a password reset link to the address. The response is the same, and takes as long,
+/// whether or not the address belongs to a user, so that it can not be used to find out who has an
+/// account. Every request counts towards the throttling of the address and the client ip.
 pub async fn forgot_password(
     r: DataRequest<String, Empty>,
+    ip: String,
     _v: u8,
 ) -> Result<impl warp::Reply, warp::Rejection> {
     let email;
@@ -20,42 +27,48 @@ pub async fn forgot_password(
         return Err(reject::custom(Fault::NoData));
     }
 
-    // Generate new password.
-    let password = util::random_string(8);
-
-    let auth_email: AuthEmail = modify(
-        AUTH_EMAIL_COLLECTION,
-        [&email],
-        &email,
-        |mut auth_email: AuthEmail| {
-            // Change password.
-            auth_email.passhash = util::hash(password.as_bytes());
-            Ok(auth_email)
-        },
-    )
-    .await?;
-
-    let (user, _etag): (User, _) =
-        get(USER_COLLECTION, [&auth_email.user_id], &auth_email.user_id).await?;
-    // Send email.
-    let mut map = SGMap::new();
-    map.insert(String::from("temporaryPassword"), password);
-
-    let p = Personalization::new(Email::new(&user.email)).add_dynamic_template_data(map);
-
-    let m = Message::new(Email::new("info@primecrime.se"))
-        .set_template_id("d-b1a6ec1654ba478b88fa035b99c72863")
-        .add_personalization(p);
-    let sender = Sender::new(SENDGRID_API_KEY.to_string());
-    match sender.send(&m).await {
-        Ok(_) => (),
-        Err(e) => {
-            log(format!("Could not send forgot password email due to {}", e));
+    throttle::check(Subject::PasswordResetIp(&ip)).await?;
+    throttle::check(Subject::PasswordReset(&email)).await?;
+    throttle::record_failure(Subject::PasswordResetIp(&ip)).await?;
+    throttle::record_failure(Subject::PasswordReset(&email)).await?;
+
+    // The lookup and the email are done in the background, so that the response does not tell
+    // whether there was anything to send.
+    tokio::spawn(async move {
+        if let Err(e) = send_reset(&email).await {
+            log(format!(
+                "Could not send forgot password email due to {:?}",
+                e
+            ));
         }
-    };
+    });
 
     Ok(warp::reply::json(&DataResponse {
         data: None::<Empty>,
         extra: None::<Empty>,
     }))
 }
+
+async fn send_reset(email: &str) -> Result<(), warp::Rejection> {
+    let auth_email: AuthEmail = match get(AUTH_EMAIL_COLLECTION, [&email], &email).await {
+        Ok((auth_email, _)) => auth_email,
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => return Ok(()),
+            _ => return Err(e.into()),
+        },
+    };
+
+    // NOTE: The password is left untouched until the reset is confirmed, so requesting a reset
+    // for someone else's address does nothing but send them an email.
+    let reset_token = util::random_string(32);
+    let reset = PasswordReset {
+        id: util::hash_token(&reset_token),
+        email: auth_email.id,
+        user_id: auth_email.user_id,
+        expires: Utc::now() + Duration::minutes(RESET_LIFETIME_MINUTES),
+        created: Utc::now(),
+    };
+    insert(PASSWORD_RESET_COLLECTION, [&reset.id], &reset, None).await?;
+
+    email::send_password_reset(email, &reset_token).await
+}
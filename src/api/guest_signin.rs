@@ -0,0 +1,131 @@
+use super::signin::Response;
+use crate::fault::Fault;
+use crate::models::{GuestDevice, User};
+use crate::throttle::{self, Subject};
+use crate::token;
+use crate::util::{self, DataRequest, DataResponse, Empty};
+use crate::{DEFAULT_OFFICE_ID, GUEST_DEVICE_COLLECTION, PRODUCTION_ENVIRONMENT, USER_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{get, insert, upsert, CosmosErrorKind};
+use std::collections::HashMap;
+use warp::reject;
+
+/// Signs in as the anonymous guest of a device, creating the guest on first use. A guest can
+/// store listening data like any user and keeps its user id when upgraded through `signup` or
+/// `signin_provider`. The number of guests created from an ip is throttled.
+pub async fn guest_signin(
+    r: DataRequest<String, Empty>,
+    ip: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let device_id = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+    if device_id.trim().is_empty() {
+        return Err(reject::custom(Fault::IllegalArgument(String::from(
+            "Device id is empty",
+        ))));
+    }
+
+    let id = util::hash_token(&device_id);
+    let guest = match get(GUEST_DEVICE_COLLECTION, [&id], &id).await {
+        Ok((guest_device, _)) => {
+            let guest_device: GuestDevice = guest_device;
+            match get(
+                USER_COLLECTION,
+                [&guest_device.user_id],
+                &guest_device.user_id,
+            )
+            .await
+            {
+                Ok((user, _)) => {
+                    let user: User = user;
+                    // An upgraded guest signs in like any other user.
+                    if user.guest && !user.deleted {
+                        Some(user)
+                    } else {
+                        None
+                    }
+                }
+                Err(e) => match e.kind {
+                    CosmosErrorKind::NotFound => None,
+                    _ => return Err(e.into()),
+                },
+            }
+        }
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => None,
+            _ => return Err(e.into()),
+        },
+    };
+    let user = match guest {
+        Some(user) => user,
+        None => {
+            throttle::check(Subject::Guest(&ip)).await?;
+            throttle::record_failure(Subject::Guest(&ip)).await?;
+            create_guest(&id).await?
+        }
+    };
+
+    let tokens = token::issue(&user, false, &ip).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&Response {
+            access_token: Some(&tokens.access_token),
+            refresh_token: Some(&tokens.refresh_token),
+            two_factor_token: None,
+            user_id: &user.id,
+        }),
+        extra: None::<Empty>,
+    }))
+}
+
+async fn create_guest(device_id: &str) -> Result<User, warp::Rejection> {
+    let user = User {
+        id: util::new_guid_v4(),
+        deleted: false,
+        // Automatically make user a test user if on the test server.
+        test: !*PRODUCTION_ENVIRONMENT,
+        guest: true,
+        guest_device: Some(device_id.to_string()),
+        roles: vec![],
+        devices: vec![],
+        images: vec![],
+        preferred_name: None,
+        last_name: String::new(),
+        middle_names: None,
+        first_name: String::new(),
+        office_ids: vec![DEFAULT_OFFICE_ID.to_string()],
+        office_joined_at: HashMap::new(),
+        deleted_office_ids: vec![],
+        saved_series: vec![],
+        email: String::new(),
+        phone: None,
+        favourite_episode_ids: vec![],
+        email_verified_at: None,
+        email_verification_sent_at: None,
+        pending_email: None,
+        token_generation: 0,
+        role_generation: 0,
+        modified: Utc::now(),
+        created: Utc::now(),
+    };
+    insert(USER_COLLECTION, [&user.id], &user, None).await?;
+
+    // Replaces the link to an earlier guest of the device that has since been upgraded.
+    let guest_device = GuestDevice {
+        id: device_id.to_string(),
+        user_id: user.id.clone(),
+        created: Utc::now(),
+    };
+    upsert(
+        GUEST_DEVICE_COLLECTION,
+        [&guest_device.id],
+        &guest_device,
+        None,
+    )
+    .await?;
+    Ok(user)
+}
@@ -0,0 +1,51 @@
+use crate::{
+    guest,
+    models::{Claims, RefreshTokenFamily, User},
+    policy::{self, Resource},
+    util::{log, DataResponse, Empty},
+    GUEST_INACTIVITY_DAYS, REFRESH_TOKEN_COLLECTION, USER_COLLECTION,
+};
+use chrono::{Duration, Utc};
+use cosmos_utils::{query_crosspartition, query_crosspartition_etag};
+
+/// Removes guests that have been inactive for `GUEST_INACTIVITY_DAYS`, i.e. have neither been
+/// modified nor signed in or refreshed a session since. Returns the number of removed guests.
+pub async fn guests_cleanup(claims: Claims, _v: u8) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(&claims, policy::SCHEDULED_JOBS_RUN, &Resource::none())?;
+
+    let cutoff = Utc::now() - Duration::days(*GUEST_INACTIVITY_DAYS);
+    let q = format!(
+        "SELECT * FROM {} u WHERE u.guest = true AND u.modified < \"{}\"",
+        USER_COLLECTION,
+        cutoff.to_rfc3339()
+    );
+    let guests: Vec<(User, String)> =
+        query_crosspartition_etag(USER_COLLECTION, [&()], q, -1, true).await?;
+
+    let mut removed: usize = 0;
+    for (user, etag) in guests {
+        let q = format!(
+            "SELECT * FROM {} f WHERE f.created >= \"{}\" OR f.refreshed >= \"{}\"",
+            REFRESH_TOKEN_COLLECTION,
+            cutoff.to_rfc3339(),
+            cutoff.to_rfc3339()
+        );
+        let active: Vec<RefreshTokenFamily> =
+            query_crosspartition(REFRESH_TOKEN_COLLECTION, [&user.id], q, 1, false).await?;
+        if !active.is_empty() {
+            continue;
+        }
+
+        // NOTE: A guest that fails to be removed, e.g. because it was upgraded meanwhile, is
+        // left for the next run.
+        match guest::remove(&user, etag).await {
+            Ok(_) => removed += 1,
+            Err(e) => log(format!("Could not remove guest {} due to {:?}", user.id, e)),
+        }
+    }
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&removed),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,73 @@
+use crate::fault::Fault;
+use crate::invitation;
+use crate::membership;
+use crate::models::{Claims, Invitation, User};
+use crate::policy::{self, Resource};
+use crate::roles;
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::{INVITATION_COLLECTION, USER_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{get, CosmosErrorKind, CosmosSaga};
+use warp::reject;
+
+/// Accepts an invitation given the token from the invitation email, adding the office to the
+/// signed in user and granting the invitation's roles. The user must have the invited email, so
+/// signing up or in comes first.
+pub async fn invitation_accept(
+    r: DataRequest<String, Empty>,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let accept_token = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+
+    let (user, user_etag): (User, _) = get(USER_COLLECTION, [&claims.sub], &claims.sub).await?;
+    policy::authorize(&claims, policy::INVITATION_ACCEPT, &Resource::user(&user))?;
+    let (invitation, invitation_etag) = invitation::find(&accept_token).await?;
+    if user.email.to_lowercase() != invitation.email {
+        return Err(reject::custom(Fault::Forbidden(String::from(
+            "The invitation was sent to another email",
+        ))));
+    }
+
+    let mut new_user = user.clone();
+    membership::add(&mut new_user, &invitation.office_id);
+    new_user.roles = roles::normalize(user.roles.iter().chain(&invitation.roles).cloned());
+    if !roles::difference(&new_user.roles, &user.roles).is_empty() {
+        new_user.role_generation += 1;
+    }
+    new_user.modified = Utc::now();
+
+    // NOTE: The invitation goes first with its etag, so that it can only be accepted once.
+    let mut saga = CosmosSaga::new();
+    saga.delete::<Invitation, _, _, _>(
+        INVITATION_COLLECTION,
+        [&invitation.id],
+        &invitation.id,
+        Some(invitation_etag),
+    )
+    .await
+    .map_err(|e| match e.kind {
+        CosmosErrorKind::PreconditionFailed | CosmosErrorKind::NotFound => {
+            reject::custom(Fault::Unauthorized)
+        }
+        _ => e.into(),
+    })?;
+    saga.upsert(
+        USER_COLLECTION,
+        [&user.id],
+        &new_user,
+        &user.id,
+        Some(&user_etag),
+    )
+    .await?;
+    saga.finalize().await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&new_user),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,94 @@
+use crate::fault::Fault;
+use crate::membership;
+use crate::models::{Claims, JoinCode, Office, User};
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::{JOIN_CODE_COLLECTION, OFFICE_COLLECTION, USER_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{get, CosmosErrorKind, CosmosSaga};
+use warp::reject;
+
+/// Joins the office of the join code as the signed in user. Redeeming a code of an office the user
+/// is already a member of does not count as a use.
+pub async fn join_code_redeem(
+    r: DataRequest<String, Empty>,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let code = if let Some(q) = r.data {
+        q.trim().to_string()
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+
+    let (mut join_code, join_code_etag): (JoinCode, _) =
+        match get(JOIN_CODE_COLLECTION, [&code], &code).await {
+            Ok(j) => j,
+            Err(e) => match e.kind {
+                CosmosErrorKind::NotFound => {
+                    return Err(reject::custom(Fault::NotFound(String::from(
+                        "No such join code",
+                    ))))
+                }
+                _ => return Err(e.into()),
+            },
+        };
+    if join_code
+        .expires
+        .is_some_and(|expires| expires <= Utc::now())
+    {
+        return Err(reject::custom(Fault::Ineligible(String::from(
+            "Join code has expired",
+        ))));
+    }
+    if join_code.max_uses.is_some_and(|max| join_code.uses >= max) {
+        return Err(reject::custom(Fault::Depleted));
+    }
+    let (office, _): (Office, _) = get(
+        OFFICE_COLLECTION,
+        [&join_code.office_id],
+        &join_code.office_id,
+    )
+    .await?;
+    if office.deleted {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "Office {} is deleted",
+            office.id
+        ))));
+    }
+
+    let (mut user, user_etag): (User, _) = get(USER_COLLECTION, [&claims.sub], &claims.sub).await?;
+    if !membership::add(&mut user, &join_code.office_id) {
+        return Ok(warp::reply::json(&DataResponse {
+            data: Some(user),
+            extra: None::<Empty>,
+        }));
+    }
+    join_code.uses += 1;
+    join_code.modified = Utc::now();
+
+    // NOTE: The join code goes first with its etag, so that concurrent redeems can not exceed
+    // the usage cap.
+    let mut saga = CosmosSaga::new();
+    saga.upsert(
+        JOIN_CODE_COLLECTION,
+        [&join_code.id],
+        &join_code,
+        &join_code.id,
+        Some(&join_code_etag),
+    )
+    .await?;
+    saga.upsert(
+        USER_COLLECTION,
+        [&user.id],
+        &user,
+        &user.id,
+        Some(&user_etag),
+    )
+    .await?;
+    saga.finalize().await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(user),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,21 @@
+use crate::token::{Jwk, VERIFICATION_KEYS};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct JwkSet<'a> {
+    pub keys: Vec<&'a Jwk>,
+}
+
+/// Publishes the public keys our tokens can be verified with (RFC 7517), so that other services
+/// can verify tokens without holding a secret. Keys are matched by the `kid` token header.
+pub async fn jwks_get() -> Result<impl warp::Reply, warp::Rejection> {
+    let jwks = JwkSet {
+        keys: VERIFICATION_KEYS.iter().map(|k| &k.jwk).collect(),
+    };
+
+    Ok(warp::reply::with_header(
+        warp::reply::json(&jwks),
+        "Cache-Control",
+        "public, max-age=3600",
+    ))
+}
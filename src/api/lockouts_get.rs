@@ -0,0 +1,22 @@
+use crate::models::{Claims, SigninAttempts};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::SIGNIN_ATTEMPT_COLLECTION;
+use cosmos_utils::query_crosspartition;
+
+/// Lists the emails and client ips that have been locked out of signin, most recent first.
+pub async fn lockouts_get(claims: Claims, _v: u8) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(&claims, policy::SECURITY_REPORTS_GET, &Resource::none())?;
+
+    let q = format!(
+        "SELECT * FROM {} a WHERE a.lockouts > 0 ORDER BY a.modified DESC",
+        SIGNIN_ATTEMPT_COLLECTION
+    );
+    let lockouts: Vec<SigninAttempts> =
+        query_crosspartition(SIGNIN_ATTEMPT_COLLECTION, [&()], q, -1, true).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&lockouts),
+        extra: None::<Empty>,
+    }))
+}
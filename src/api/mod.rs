@@ -2,10 +2,23 @@ mod user_get;
 pub use user_get::user_get;
 mod user_delete;
 pub use user_delete::user_delete;
+mod user_impersonate;
+pub use user_impersonate::user_impersonate;
+
+mod user_office_put;
+pub use user_office_put::user_office_put;
+
+mod user_office_delete;
+pub use user_office_delete::user_office_delete;
+
 mod user_put;
 pub use user_put::user_put;
+mod user_roles_post;
+pub use user_roles_post::user_roles_post;
 mod user_roles_put;
 pub use user_roles_put::user_roles_put;
+mod user_roles_revoke;
+pub use user_roles_revoke::user_roles_revoke;
 mod user_device_post;
 pub use user_device_post::user_device_post;
 mod user_image_put;
@@ -16,22 +29,135 @@ mod office_poll;
 pub use office_poll::office_poll;
 mod get_all_offices;
 pub use get_all_offices::get_all_offices;
+mod office_restore;
+pub use office_restore::office_restore;
+
+mod office_deletion_get;
+pub use office_deletion_get::office_deletion_get;
+
+mod office_audit_get;
+pub use office_audit_get::office_audit_get;
+
+mod audit_get;
+pub use audit_get::audit_get;
+
+mod office_settings_put;
+pub use office_settings_put::office_settings_put;
+
+mod office_settings_image_put;
+pub use office_settings_image_put::office_settings_image_put;
+
+mod office_products_get;
+pub use office_products_get::office_products_get;
+
+mod office_product_post;
+pub use office_product_post::office_product_post;
+
+mod office_product_put;
+pub use office_product_put::office_product_put;
+
+mod office_product_delete;
+pub use office_product_delete::office_product_delete;
+
+mod office_join_code_post;
+pub use office_join_code_post::office_join_code_post;
+
+mod office_join_codes_get;
+pub use office_join_codes_get::office_join_codes_get;
+
+mod office_join_code_delete;
+pub use office_join_code_delete::office_join_code_delete;
+
+mod join_code_redeem;
+pub use join_code_redeem::join_code_redeem;
+
+mod office_invitation_post;
+pub use office_invitation_post::office_invitation_post;
+mod office_invitations_get;
+pub use office_invitations_get::office_invitations_get;
+mod office_invitation_resend;
+pub use office_invitation_resend::office_invitation_resend;
+mod office_invitation_delete;
+pub use office_invitation_delete::office_invitation_delete;
+mod invitation_accept;
+pub use invitation_accept::invitation_accept;
 mod signup;
 pub use signup::signup;
 mod signin;
 pub use signin::signin;
+mod signin_provider;
+pub use signin_provider::signin_provider;
+mod guest_signin;
+pub use guest_signin::guest_signin;
+mod signin_two_factor;
+pub use signin_two_factor::signin_two_factor;
+mod request_signin_code;
+pub use request_signin_code::request_signin_code;
+mod signin_code;
+pub use signin_code::signin_code;
 mod change_password;
 pub use change_password::change_password;
 mod forgot_password;
 pub use forgot_password::forgot_password;
+mod reset_password;
+pub use reset_password::reset_password;
+mod verify_email;
+pub use verify_email::verify_email;
+mod resend_verification_email;
+pub use resend_verification_email::resend_verification_email;
+mod user_email_post;
+pub use user_email_post::user_email_post;
+mod confirm_email_change;
+pub use confirm_email_change::confirm_email_change;
 mod refresh_token;
 pub use refresh_token::refresh_token;
+mod signout;
+pub use signout::signout;
+mod signout_all;
+pub use signout_all::signout_all;
+mod user_sessions_get;
+pub use user_sessions_get::user_sessions_get;
+mod user_session_delete;
+pub use user_session_delete::user_session_delete;
+mod user_identity_post;
+pub use user_identity_post::user_identity_post;
+mod user_identities_get;
+pub use user_identities_get::user_identities_get;
+mod user_identity_delete;
+pub use user_identity_delete::user_identity_delete;
+mod two_factor_post;
+pub use two_factor_post::two_factor_post;
+mod two_factor_confirm;
+pub use two_factor_confirm::two_factor_confirm;
+mod two_factor_disable;
+pub use two_factor_disable::two_factor_disable;
+mod service_account_post;
+pub use service_account_post::service_account_post;
+mod service_accounts_get;
+pub use service_accounts_get::service_accounts_get;
+mod service_account_delete;
+pub use service_account_delete::service_account_delete;
+mod password_hash_report_get;
+pub use password_hash_report_get::password_hash_report_get;
+mod lockouts_get;
+pub use lockouts_get::lockouts_get;
+mod jwks_get;
+pub use jwks_get::jwks_get;
+mod office_content_post;
+pub use office_content_post::office_content_post;
+mod office_content_put;
+pub use office_content_put::office_content_put;
+mod office_content_delete;
+pub use office_content_delete::office_content_delete;
 mod episode_get;
 mod episode_image_put;
 mod episode_metadata_post;
 mod episode_metadata_put;
 mod episode_post;
 mod episode_recording_put;
+mod office_delete;
+mod office_post;
+mod office_put;
 mod series_image_put;
 mod series_user_data_post;
 
@@ -47,5 +173,11 @@ pub use webhook_subscription_apple::webhook_subscription_apple;
 mod cron;
 pub use cron::cron;
 
+mod guests_cleanup;
+pub use guests_cleanup::guests_cleanup;
+
+mod office_deletions_resume;
+pub use office_deletions_resume::office_deletions_resume;
+
 mod new_users_email;
 pub use new_users_email::new_users_email;
@@ -1,28 +1,17 @@
 use crate::{
     fault::Fault,
-    models::User,
-    util::{log, DataRequest, DataResponse, Empty},
-    CRON_SECRET, SENDGRID_API_KEY, USER_COLLECTION,
+    models::{Claims, User},
+    policy::{self, Resource},
+    util::{log, DataResponse, Empty},
+    SENDGRID_API_KEY, USER_COLLECTION,
 };
-use chrono::{Duration, Timelike, Utc};
+use chrono::{Timelike, Utc};
 use cosmos_utils::query_crosspartition;
 use sendgrid::v3::*;
 use warp::reject;
 
-pub async fn new_users_email(
-    r: DataRequest<Empty, String>,
-    _v: u8,
-) -> Result<impl warp::Reply, warp::Rejection> {
-    match r.extra {
-        Some(secret) => {
-            if &secret != &*CRON_SECRET {
-                return Err(reject::custom(Fault::Unauthorized));
-            }
-        }
-        None => {
-            return Err(reject::custom(Fault::NoExtra));
-        }
-    };
+pub async fn new_users_email(claims: Claims, _v: u8) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(&claims, policy::SCHEDULED_JOBS_RUN, &Resource::none())?;
 
     let end = Utc::now();
     // Set the start to the beginnig of the day
@@ -0,0 +1,31 @@
+use crate::audit;
+use crate::models::{AuditQuery, Claims};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+
+/// Lists the audit log of the office, most recent first, optionally filtered by actor and time
+/// range.
+pub async fn office_audit_get(
+    office_id: String,
+    filter: AuditQuery,
+    claims: Claims,
+    _v: u8,
+    range: u16,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::OFFICE_AUDIT_GET,
+        &Resource::office(&office_id),
+    )?;
+
+    let filter = AuditQuery {
+        office_id: Some(office_id),
+        ..filter
+    };
+    let entries = audit::query(&filter, range).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&entries),
+        extra: None::<Empty>,
+    }))
+}
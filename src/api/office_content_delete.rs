@@ -0,0 +1,41 @@
+use crate::audit;
+use crate::models::{Claims, OfficeContent};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use cosmos_utils::modify_async_get_old;
+use futures::future;
+
+/// Marks a document of an office's content as deleted, e.g.
+/// `api::office_content_delete::<Series>`. A concurrent change is retried rather than rejected.
+pub async fn office_content_delete<T: OfficeContent>(
+    office_id: String,
+    id: String,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let resource = Resource::office(&office_id);
+    policy::authorize(&claims, T::DELETE, &resource)?;
+
+    let (instance, old_instance, _etag) =
+        modify_async_get_old(T::COLLECTION, [&office_id], &id, |mut instance: T| {
+            instance.mark_deleted();
+            future::ok(instance)
+        })
+        .await?;
+
+    audit::record(
+        &request_id,
+        &claims,
+        T::DELETE,
+        instance.id(),
+        &resource,
+        audit::changes(Some(&old_instance), Some(&instance)),
+    )
+    .await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(instance),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,50 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::{Claims, OfficeContent};
+use crate::policy::{self, Resource};
+use crate::util::{DataRequest, DataResponse, Empty};
+use cosmos_utils::insert;
+use warp::reject;
+
+/// Posts a document of an office's content, e.g. `api::office_content_post::<Series>`.
+pub async fn office_content_post<T: OfficeContent>(
+    office_id: String,
+    r: DataRequest<T, Empty>,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let mut instance;
+    if let Some(q) = r.data {
+        instance = q;
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    }
+    if instance.office_id() != office_id {
+        return Err(reject::custom(Fault::IllegalArgument(format!(
+            "office_id does not match url ({} != {}).",
+            instance.office_id(),
+            office_id
+        ))));
+    }
+    let resource = Resource::office(&office_id);
+    policy::authorize(&claims, T::POST, &resource)?;
+    instance.set_id(uuid::Uuid::new_v4().to_string());
+    instance.prepare(None);
+    insert(T::COLLECTION, [&office_id], &instance, None).await?;
+
+    audit::record(
+        &request_id,
+        &claims,
+        T::POST,
+        instance.id(),
+        &resource,
+        audit::changes(None, Some(&instance)),
+    )
+    .await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(instance),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,65 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::{Claims, OfficeContent};
+use crate::policy::{self, Resource};
+use crate::util::{DataRequest, DataResponse, Empty};
+use cosmos_utils::modify_async_get_old;
+use futures::future;
+use warp::reject;
+
+/// Replaces a document of an office's content, e.g. `api::office_content_put::<Series>`. A
+/// concurrent change is retried rather than rejected.
+pub async fn office_content_put<T: OfficeContent>(
+    office_id: String,
+    id: String,
+    r: DataRequest<T, Empty>,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let new_instance;
+    if let Some(q) = r.data {
+        new_instance = q;
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    }
+    let resource = Resource::office(&office_id);
+    policy::authorize(&claims, T::PUT, &resource)?;
+    if new_instance.id() != id {
+        return Err(reject::custom(Fault::IllegalArgument(format!(
+            "id does not match url ({} != {}).",
+            new_instance.id(),
+            id
+        ))));
+    }
+    if new_instance.office_id() != office_id {
+        return Err(reject::custom(Fault::IllegalArgument(format!(
+            "office_id does not match url ({} != {}).",
+            new_instance.office_id(),
+            office_id
+        ))));
+    }
+
+    let (instance, old_instance, _etag) =
+        modify_async_get_old(T::COLLECTION, [&office_id], &id, |old_instance: T| {
+            let mut instance = new_instance.clone();
+            instance.prepare(Some(&old_instance));
+            future::ok(instance)
+        })
+        .await?;
+
+    audit::record(
+        &request_id,
+        &claims,
+        T::PUT,
+        instance.id(),
+        &resource,
+        audit::changes(Some(&old_instance), Some(&instance)),
+    )
+    .await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(instance),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,95 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::{Claims, Office, OfficeDeletion};
+use crate::office_deletion;
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::{OFFICE_COLLECTION, OFFICE_DELETION_COLLECTION};
+use chrono::{Duration, Utc};
+use cosmos_utils::{get, upsert, CosmosErrorKind};
+use warp::reject;
+
+impl Office {
+    /// Starts deleting the office along with its content, subscriptions and memberships, returns
+    /// the deletion whose progress is polled through `office_deletion_get`.
+    pub async fn delete(
+        office_id: String,
+        claims: Claims,
+        request_id: String,
+        _v: u8,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let resource = Resource::office(&office_id);
+        policy::authorize(&claims, policy::OFFICE_DELETE, &resource)?;
+
+        let (office, _): (Office, _) = get(OFFICE_COLLECTION, [&office_id], &office_id).await?;
+        let (old_job, etag) = match get::<OfficeDeletion, _, _, _>(
+            OFFICE_DELETION_COLLECTION,
+            [&office_id],
+            &office_id,
+        )
+        .await
+        {
+            Ok((job, etag)) => (Some(job), Some(etag)),
+            Err(e) => match e.kind {
+                CosmosErrorKind::NotFound => (None, None),
+                _ => return Err(e.into()),
+            },
+        };
+        if let Some(old_job) = &old_job {
+            if old_job.finished.is_none() {
+                return Err(reject::custom(Fault::IllegalState(format!(
+                    "Office {} is already being {}",
+                    office_id,
+                    if old_job.restore {
+                        "restored"
+                    } else {
+                        "deleted"
+                    }
+                ))));
+            }
+            if office.deleted && !old_job.restore {
+                return Err(reject::custom(Fault::IllegalState(format!(
+                    "Office {} is already deleted",
+                    office_id
+                ))));
+            }
+        }
+
+        let now = Utc::now();
+        let job = OfficeDeletion {
+            id: office_id.clone(),
+            restore: false,
+            completed_steps: vec![],
+            total_steps: office_deletion::STEPS.len(),
+            processed: 0,
+            requested_by: claims.sub.clone(),
+            restorable_until: now + Duration::days(office_deletion::RETENTION_DAYS),
+            finished: None,
+            modified: now,
+            created: now,
+        };
+        upsert(
+            OFFICE_DELETION_COLLECTION,
+            [&office_id],
+            &job,
+            etag.as_deref(),
+        )
+        .await?;
+        tokio::spawn(office_deletion::run(office_id.clone()));
+
+        audit::record(
+            &request_id,
+            &claims,
+            policy::OFFICE_DELETE,
+            &office_id,
+            &resource,
+            audit::changes(old_job.as_ref(), Some(&job)),
+        )
+        .await;
+
+        Ok(warp::reply::json(&DataResponse {
+            data: Some(job),
+            extra: None::<Empty>,
+        }))
+    }
+}
@@ -0,0 +1,26 @@
+use crate::models::{Claims, OfficeDeletion};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::OFFICE_DELETION_COLLECTION;
+use cosmos_utils::get;
+
+/// The progress of the office's latest deletion or restore.
+pub async fn office_deletion_get(
+    office_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::OFFICE_DELETE,
+        &Resource::office(&office_id),
+    )?;
+
+    let (job, _): (OfficeDeletion, _) =
+        get(OFFICE_DELETION_COLLECTION, [&office_id], &office_id).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(job),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,37 @@
+use crate::{
+    models::{Claims, OfficeDeletion},
+    office_deletion,
+    policy::{self, Resource},
+    util::{DataResponse, Empty},
+    OFFICE_DELETION_COLLECTION,
+};
+use chrono::{Duration, Utc};
+use cosmos_utils::query_crosspartition;
+
+/// Resumes the office deletions and restores that have stopped, i.e. have not finished nor made
+/// progress for `office_deletion::STALLED_AFTER_MINUTES`. Returns the number of resumed jobs.
+pub async fn office_deletions_resume(
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(&claims, policy::SCHEDULED_JOBS_RUN, &Resource::none())?;
+
+    let cutoff = Utc::now() - Duration::minutes(office_deletion::STALLED_AFTER_MINUTES);
+    let q = format!(
+        "SELECT * FROM {} o WHERE NOT IS_DEFINED(o.finished) AND o.modified < \"{}\"",
+        OFFICE_DELETION_COLLECTION,
+        cutoff.to_rfc3339()
+    );
+    let jobs: Vec<OfficeDeletion> =
+        query_crosspartition(OFFICE_DELETION_COLLECTION, [&()], q, -1, true).await?;
+
+    let resumed = jobs.len();
+    for job in jobs {
+        tokio::spawn(office_deletion::run(job.id));
+    }
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&resumed),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,42 @@
+use crate::fault::Fault;
+use crate::models::{Claims, Invitation};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::INVITATION_COLLECTION;
+use cosmos_utils::{delete, get};
+use warp::reject;
+
+/// Revokes a pending invitation, its accept token stops working.
+pub async fn office_invitation_delete(
+    office_id: String,
+    invitation_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::OFFICE_INVITATIONS_MANAGE,
+        &Resource::office(&office_id),
+    )?;
+
+    let (invitation, etag): (Invitation, _) =
+        get(INVITATION_COLLECTION, [&invitation_id], &invitation_id).await?;
+    if invitation.office_id != office_id {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "Office {} has no invitation {}",
+            office_id, invitation_id
+        ))));
+    }
+    delete(
+        INVITATION_COLLECTION,
+        [&invitation_id],
+        &invitation_id,
+        Some(etag),
+    )
+    .await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
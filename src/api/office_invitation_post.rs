@@ -0,0 +1,92 @@
+use crate::fault::Fault;
+use crate::invitation;
+use crate::models::{Claims, Invitation, Office, Role};
+use crate::policy::{self, Resource};
+use crate::roles;
+use crate::util::{self, DataRequest, DataResponse, Empty};
+use crate::{INVITATION_COLLECTION, OFFICE_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{get, query_crosspartition_etag};
+use warp::reject;
+
+/// Invites an email address to the office, granting the office roles in `extra` once accepted.
+/// Inviting an address that already has a pending invitation to the office replaces its roles and
+/// sends it again.
+pub async fn office_invitation_post(
+    office_id: String,
+    r: DataRequest<String, Vec<Role>>,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let email = if let Some(q) = r.data {
+        // Normalise email.
+        q.trim().to_lowercase()
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+    if email.is_empty() {
+        return Err(reject::custom(Fault::IllegalArgument(String::from(
+            "Email is empty",
+        ))));
+    }
+
+    policy::authorize(
+        &claims,
+        policy::OFFICE_INVITATIONS_MANAGE,
+        &Resource::office(&office_id),
+    )?;
+    let granted = roles::normalize(r.extra.unwrap_or_default());
+    if granted
+        .iter()
+        .any(|role| role.sub.as_deref() != Some(office_id.as_str()))
+    {
+        return Err(reject::custom(Fault::IllegalArgument(String::from(
+            "Invitations can only grant roles for the office",
+        ))));
+    }
+    roles::authorize(&claims, &granted)?;
+
+    let (office, _): (Office, _) = get(OFFICE_COLLECTION, [&office_id], &office_id).await?;
+    if office.deleted {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "Office {} is deleted",
+            office_id
+        ))));
+    }
+
+    let q = format!(
+        "SELECT * FROM {} i WHERE i.officeId = '{}' AND i.email = '{}'",
+        INVITATION_COLLECTION,
+        office_id,
+        email.replace('\'', "\\'")
+    );
+    let pending: Vec<(Invitation, String)> =
+        query_crosspartition_etag(INVITATION_COLLECTION, [&()], q, 1, true).await?;
+    let invitation = match pending.into_iter().next() {
+        Some((mut invitation, etag)) => {
+            invitation.roles = granted;
+            invitation.invited_by = claims.sub.clone();
+            invitation::send(invitation, Some(&etag)).await?
+        }
+        None => {
+            let invitation = Invitation {
+                id: util::new_guid_v4(),
+                office_id,
+                email,
+                roles: granted,
+                secret_hash: String::new(),
+                invited_by: claims.sub.clone(),
+                expires: Utc::now(),
+                sent: Utc::now(),
+                modified: Utc::now(),
+                created: Utc::now(),
+            };
+            invitation::send(invitation, None).await?
+        }
+    };
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&invitation),
+        extra: None::<Empty>,
+    }))
+}
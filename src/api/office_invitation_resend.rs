@@ -0,0 +1,37 @@
+use crate::fault::Fault;
+use crate::invitation;
+use crate::models::{Claims, Invitation};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::INVITATION_COLLECTION;
+use cosmos_utils::get;
+use warp::reject;
+
+/// Sends the invitation again with a new accept token and expiry, the earlier token stops working.
+pub async fn office_invitation_resend(
+    office_id: String,
+    invitation_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::OFFICE_INVITATIONS_MANAGE,
+        &Resource::office(&office_id),
+    )?;
+
+    let (invitation, etag): (Invitation, _) =
+        get(INVITATION_COLLECTION, [&invitation_id], &invitation_id).await?;
+    if invitation.office_id != office_id {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "Office {} has no invitation {}",
+            office_id, invitation_id
+        ))));
+    }
+    let invitation = invitation::send(invitation, Some(&etag)).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&invitation),
+        extra: None::<Empty>,
+    }))
+}
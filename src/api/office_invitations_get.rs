@@ -0,0 +1,34 @@
+use crate::models::{Claims, Invitation};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::INVITATION_COLLECTION;
+use cosmos_utils::query_crosspartition;
+
+/// Lists the office's pending invitations, most recently sent first. Expired invitations are
+/// included until revoked, they can be sent again.
+pub async fn office_invitations_get(
+    office_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::OFFICE_INVITATIONS_MANAGE,
+        &Resource::office(&office_id),
+    )?;
+
+    let q = format!(
+        "SELECT * FROM {} i WHERE i.officeId = '{}' ORDER BY i.sent DESC",
+        INVITATION_COLLECTION, office_id
+    );
+    let mut invitations: Vec<Invitation> =
+        query_crosspartition(INVITATION_COLLECTION, [&()], q, -1, true).await?;
+    for invitation in &mut invitations {
+        invitation.secret_hash = String::new();
+    }
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&invitations),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,35 @@
+use crate::fault::Fault;
+use crate::models::{Claims, JoinCode};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::JOIN_CODE_COLLECTION;
+use cosmos_utils::{delete, get};
+use warp::reject;
+
+/// Revokes a join code, users that already joined with it stay members.
+pub async fn office_join_code_delete(
+    office_id: String,
+    code: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::OFFICE_JOIN_CODES_MANAGE,
+        &Resource::office(&office_id),
+    )?;
+
+    let (join_code, etag): (JoinCode, _) = get(JOIN_CODE_COLLECTION, [&code], &code).await?;
+    if join_code.office_id != office_id {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "Office {} has no join code {}",
+            office_id, code
+        ))));
+    }
+    delete(JOIN_CODE_COLLECTION, [&code], &code, Some(etag)).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
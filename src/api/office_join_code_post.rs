@@ -0,0 +1,43 @@
+use crate::fault::Fault;
+use crate::membership;
+use crate::models::{Claims, JoinCode, Office};
+use crate::policy::{self, Resource};
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::{JOIN_CODE_COLLECTION, OFFICE_COLLECTION};
+use cosmos_utils::{get, insert};
+use warp::reject;
+
+/// Creates a join code for the office, optionally expiring or capped to a number of uses.
+pub async fn office_join_code_post(
+    office_id: String,
+    r: DataRequest<JoinCode, Empty>,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let requested = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+
+    policy::authorize(
+        &claims,
+        policy::OFFICE_JOIN_CODES_MANAGE,
+        &Resource::office(&office_id),
+    )?;
+    let (office, _): (Office, _) = get(OFFICE_COLLECTION, [&office_id], &office_id).await?;
+    if office.deleted {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "Office {} is deleted",
+            office_id
+        ))));
+    }
+
+    let join_code = membership::new_join_code(&office_id, requested, &claims.sub)?;
+    insert(JOIN_CODE_COLLECTION, [&join_code.id], &join_code, None).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&join_code),
+        extra: None::<Empty>,
+    }))
+}
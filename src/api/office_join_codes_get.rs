@@ -0,0 +1,32 @@
+use crate::models::{Claims, JoinCode};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::JOIN_CODE_COLLECTION;
+use cosmos_utils::query_crosspartition;
+
+/// Lists the office's join codes, most recent first. Expired and used up codes are included until
+/// revoked.
+pub async fn office_join_codes_get(
+    office_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::OFFICE_JOIN_CODES_MANAGE,
+        &Resource::office(&office_id),
+    )?;
+
+    let q = format!(
+        "SELECT * FROM {} c WHERE c.officeId = {} ORDER BY c.created DESC",
+        JOIN_CODE_COLLECTION,
+        serde_json::json!(office_id)
+    );
+    let join_codes: Vec<JoinCode> =
+        query_crosspartition(JOIN_CODE_COLLECTION, [&()], q, -1, true).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&join_codes),
+        extra: None::<Empty>,
+    }))
+}
@@ -1,12 +1,14 @@
 use crate::fault::Fault;
-use crate::models::{Category, Claims, Episode, Office, Recommendation, RoleFlags, Series};
-use crate::util::{self, has_role, DataResponse, Empty};
+use crate::models::{Category, Claims, Episode, Office, OfficeSettings, Recommendation, Series};
+use crate::policy::{self, Resource};
+use crate::product_catalog;
+use crate::util::{self, DataResponse, Empty};
 use crate::{
-    CATEGORY_COLLECTION, EPISODE_COLLECTION, OFFICE_COLLECTION, RECOMMENDED_COLLECTION,
-    SERIES_COLLECTION,
+    CATEGORY_COLLECTION, EPISODE_COLLECTION, OFFICE_COLLECTION, OFFICE_SETTINGS_COLLECTION,
+    RECOMMENDED_COLLECTION, SERIES_COLLECTION,
 };
 use chrono::{DateTime, Utc};
-use cosmos_utils::{get, query, CosmosErrorStruct};
+use cosmos_utils::{get, query, CosmosErrorKind, CosmosErrorStruct};
 use serde::Serialize;
 use warp::{
     http::{header, Response},
@@ -19,6 +21,8 @@ pub struct OfficePollDataResponse<'a> {
     #[serde(skip_serializing_if = "util::is_none")]
     pub office: Option<&'a Office>,
     #[serde(skip_serializing_if = "util::is_none")]
+    pub settings: Option<&'a OfficeSettings>,
+    #[serde(skip_serializing_if = "util::is_none")]
     pub recommendation: Option<&'a Recommendation>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub categories: Vec<Category>,
@@ -35,12 +39,7 @@ pub async fn office_poll(
     _range: u16,
     since: Option<DateTime<Utc>>,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    if !has_role(Some(&office_id), &claims, RoleFlags::OFFICE_CONTENT_ADMIN) {
-        return Err(reject::custom(Fault::Forbidden(format!(
-            "User is not an office content admin for {}.",
-            office_id,
-        ))));
-    }
+    policy::authorize(&claims, policy::OFFICE_POLL, &Resource::office(&office_id))?;
 
     // Office
     let (office, _): (Office, _) = get(OFFICE_COLLECTION, [&office_id], &office_id).await?;
@@ -58,6 +57,22 @@ pub async fn office_poll(
     }
     let office = new_office;
 
+    // Settings, an office without settings has none to send.
+    let settings =
+        match get::<OfficeSettings, _, _, _>(OFFICE_SETTINGS_COLLECTION, [&office_id], &office_id)
+            .await
+        {
+            Ok((settings, _)) => Some(settings),
+            Err(e) => match e.kind {
+                CosmosErrorKind::NotFound => None,
+                _ => return Err(e.into()),
+            },
+        };
+    let mut settings = settings.filter(|s| since.is_none_or(|since| s.modified >= since));
+    if let Some(settings) = &mut settings {
+        settings.product_ids = product_catalog::product_ids(&office_id).await?;
+    }
+
     let since = match since {
         Some(since) => format!(" AND o.modified >= {}", since.timestamp()),
         None => String::from(""),
@@ -111,6 +126,7 @@ pub async fn office_poll(
     let res = match serde_json::to_string(&DataResponse {
         data: Some(&OfficePollDataResponse {
             office,
+            settings: settings.as_ref(),
             categories,
             series,
             episodes,
@@ -0,0 +1,44 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::{Claims, Office};
+use crate::policy::{self, Resource};
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::OFFICE_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::insert;
+use warp::reject;
+
+impl Office {
+    pub async fn post(
+        r: DataRequest<Office, Empty>,
+        claims: Claims,
+        request_id: String,
+        _v: u8,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let mut instance;
+        if let Some(q) = r.data {
+            instance = q;
+        } else {
+            return Err(reject::custom(Fault::NoData));
+        }
+        policy::authorize(&claims, policy::OFFICE_POST, &Resource::none())?;
+        instance.id = uuid::Uuid::new_v4().to_string();
+        instance.modified = Utc::now();
+        insert(OFFICE_COLLECTION, [&instance.id], &instance, None).await?;
+
+        audit::record(
+            &request_id,
+            &claims,
+            policy::OFFICE_POST,
+            &instance.id,
+            &Resource::office(&instance.id),
+            audit::changes(None, Some(&instance)),
+        )
+        .await;
+
+        Ok(warp::reply::json(&DataResponse {
+            data: Some(instance),
+            extra: None::<Empty>,
+        }))
+    }
+}
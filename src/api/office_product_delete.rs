@@ -0,0 +1,40 @@
+use crate::audit;
+use crate::models::{Claims, Product};
+use crate::policy::{self, Resource};
+use crate::product_catalog;
+use crate::util::{DataResponse, Empty};
+use crate::PRODUCT_COLLECTION;
+use cosmos_utils::{delete, get};
+
+/// Removes a product from the catalog, new purchases of it are refused while existing
+/// subscriptions are kept.
+pub async fn office_product_delete(
+    office_id: String,
+    product_id: String,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let resource = Resource::office(&office_id);
+    policy::authorize(&claims, policy::OFFICE_PRODUCTS_MANAGE, &resource)?;
+
+    let (product, etag): (Product, _) = get(PRODUCT_COLLECTION, [&office_id], &product_id).await?;
+    delete(PRODUCT_COLLECTION, [&office_id], &product_id, Some(etag)).await?;
+    product_catalog::release(&product).await;
+    product_catalog::touch_settings(&office_id).await;
+
+    audit::record(
+        &request_id,
+        &claims,
+        policy::OFFICE_PRODUCTS_MANAGE,
+        &product.id,
+        &resource,
+        audit::changes(Some(&product), None),
+    )
+    .await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,63 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::{Claims, Office, Product};
+use crate::policy::{self, Resource};
+use crate::product_catalog;
+use crate::util::{self, DataRequest, DataResponse, Empty};
+use crate::{OFFICE_COLLECTION, PRODUCT_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{get, insert};
+use warp::reject;
+
+/// Adds a store product to the catalog, purchasing it then entitles to the office.
+pub async fn office_product_post(
+    office_id: String,
+    r: DataRequest<Product, Empty>,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let mut product = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+
+    let resource = Resource::office(&office_id);
+    policy::authorize(&claims, policy::OFFICE_PRODUCTS_MANAGE, &resource)?;
+    let (office, _): (Office, _) = get(OFFICE_COLLECTION, [&office_id], &office_id).await?;
+    if office.deleted {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "Office {} is deleted",
+            office_id
+        ))));
+    }
+
+    let now = Utc::now();
+    product.id = util::new_guid_v4();
+    product.office_id = office_id.clone();
+    product.modified = now;
+    product.created = now;
+    product_catalog::validate(&product)?;
+    product_catalog::reserve(&product).await?;
+    if let Err(e) = insert(PRODUCT_COLLECTION, [&office_id], &product, None).await {
+        product_catalog::release(&product).await;
+        return Err(e.into());
+    }
+    product_catalog::touch_settings(&office_id).await;
+
+    audit::record(
+        &request_id,
+        &claims,
+        policy::OFFICE_PRODUCTS_MANAGE,
+        &product.id,
+        &resource,
+        audit::changes(None, Some(&product)),
+    )
+    .await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&product),
+        extra: None::<Empty>,
+    }))
+}
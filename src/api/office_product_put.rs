@@ -0,0 +1,85 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::{Claims, Product};
+use crate::policy::{self, Resource};
+use crate::product_catalog;
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::PRODUCT_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::{get, upsert};
+use warp::reject;
+
+/// Changes a product in the catalog. A product is moved to another office by removing it and
+/// adding it to the other office.
+pub async fn office_product_put(
+    office_id: String,
+    product_id: String,
+    r: DataRequest<Product, Empty>,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let mut product = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+
+    let resource = Resource::office(&office_id);
+    policy::authorize(&claims, policy::OFFICE_PRODUCTS_MANAGE, &resource)?;
+    if product.id != product_id {
+        return Err(reject::custom(Fault::IllegalArgument(format!(
+            "id does not match url ({} != {}).",
+            product.id, product_id
+        ))));
+    }
+    if product.office_id != office_id {
+        return Err(reject::custom(Fault::IllegalArgument(format!(
+            "office_id does not match url ({} != {}).",
+            product.office_id, office_id
+        ))));
+    }
+
+    let (old_product, etag): (Product, _) =
+        get(PRODUCT_COLLECTION, [&office_id], &product_id).await?;
+    product.created = old_product.created;
+    product.modified = Utc::now();
+    product_catalog::validate(&product)?;
+    // A changed store product is reserved before the product is saved and the old one released
+    // after, so that the product always holds the key of what it is saved as.
+    let store_product_changed =
+        product_catalog::key(product.platform, &product.product_id, &product.package_name)
+            != product_catalog::key(
+                old_product.platform,
+                &old_product.product_id,
+                &old_product.package_name,
+            );
+    if store_product_changed {
+        product_catalog::reserve(&product).await?;
+    }
+    if let Err(e) = upsert(PRODUCT_COLLECTION, [&office_id], &product, Some(&etag)).await {
+        if store_product_changed {
+            product_catalog::release(&product).await;
+        }
+        return Err(e.into());
+    }
+    if store_product_changed {
+        product_catalog::release(&old_product).await;
+    }
+    product_catalog::touch_settings(&office_id).await;
+
+    audit::record(
+        &request_id,
+        &claims,
+        policy::OFFICE_PRODUCTS_MANAGE,
+        &product.id,
+        &resource,
+        audit::changes(Some(&old_product), Some(&product)),
+    )
+    .await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&product),
+        extra: None::<Empty>,
+    }))
+}
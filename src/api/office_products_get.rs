@@ -0,0 +1,26 @@
+use crate::models::{Claims, Product};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::PRODUCT_COLLECTION;
+use cosmos_utils::query;
+
+/// Lists the office's products in the catalog.
+pub async fn office_products_get(
+    office_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::OFFICE_PRODUCTS_GET,
+        &Resource::office(&office_id),
+    )?;
+
+    let q = format!("SELECT * FROM {} p", PRODUCT_COLLECTION);
+    let products: Vec<Product> = query(PRODUCT_COLLECTION, [&office_id], q, -1).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&products),
+        extra: None::<Empty>,
+    }))
+}
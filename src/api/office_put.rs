@@ -0,0 +1,58 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::{Claims, Office};
+use crate::policy::{self, Resource};
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::OFFICE_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::modify_async_get_old;
+use futures::future;
+use warp::reject;
+
+impl Office {
+    pub async fn put(
+        office_id: String,
+        r: DataRequest<Office, Empty>,
+        claims: Claims,
+        request_id: String,
+        _v: u8,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let new_instance;
+        if let Some(q) = r.data {
+            new_instance = q;
+        } else {
+            return Err(reject::custom(Fault::NoData));
+        }
+        let resource = Resource::office(&office_id);
+        policy::authorize(&claims, policy::OFFICE_PUT, &resource)?;
+        if new_instance.id != office_id {
+            return Err(reject::custom(Fault::IllegalArgument(format!(
+                "id does not match url ({} != {}).",
+                new_instance.id, office_id
+            ))));
+        }
+
+        let (instance, old_instance, _etag) =
+            modify_async_get_old(OFFICE_COLLECTION, [&office_id], &office_id, |_: Office| {
+                let mut instance = new_instance.clone();
+                instance.modified = Utc::now();
+                future::ok(instance)
+            })
+            .await?;
+
+        audit::record(
+            &request_id,
+            &claims,
+            policy::OFFICE_PUT,
+            &instance.id,
+            &resource,
+            audit::changes(Some(&old_instance), Some(&instance)),
+        )
+        .await;
+
+        Ok(warp::reply::json(&DataResponse {
+            data: Some(instance),
+            extra: None::<Empty>,
+        }))
+    }
+}
@@ -0,0 +1,81 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::{Claims, OfficeDeletion};
+use crate::office_deletion;
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::OFFICE_DELETION_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::{get, upsert, CosmosErrorKind};
+use warp::reject;
+
+/// Starts restoring a deleted office, along with what was deleted with it, within
+/// `office_deletion::RETENTION_DAYS` of the deletion.
+pub async fn office_restore(
+    office_id: String,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let resource = Resource::office(&office_id);
+    policy::authorize(&claims, policy::OFFICE_RESTORE, &resource)?;
+
+    let (old_job, etag): (OfficeDeletion, _) =
+        match get(OFFICE_DELETION_COLLECTION, [&office_id], &office_id).await {
+            Ok(found) => found,
+            Err(e) => match e.kind {
+                CosmosErrorKind::NotFound => {
+                    return Err(reject::custom(Fault::NotFound(format!(
+                        "Office {} has not been deleted",
+                        office_id
+                    ))));
+                }
+                _ => return Err(e.into()),
+            },
+        };
+    if old_job.restore {
+        return Err(reject::custom(Fault::IllegalState(format!(
+            "Office {} is already restored",
+            office_id
+        ))));
+    }
+    if old_job.finished.is_none() {
+        return Err(reject::custom(Fault::IllegalState(format!(
+            "Office {} is still being deleted",
+            office_id
+        ))));
+    }
+    let now = Utc::now();
+    if old_job.restorable_until < now {
+        return Err(reject::custom(Fault::Ineligible(format!(
+            "Office {} was deleted more than {} days ago",
+            office_id,
+            office_deletion::RETENTION_DAYS
+        ))));
+    }
+
+    let mut job = old_job.clone();
+    job.restore = true;
+    job.completed_steps = vec![];
+    job.processed = 0;
+    job.requested_by = claims.sub.clone();
+    job.finished = None;
+    job.modified = now;
+    upsert(OFFICE_DELETION_COLLECTION, [&office_id], &job, Some(&etag)).await?;
+    tokio::spawn(office_deletion::run(office_id.clone()));
+
+    audit::record(
+        &request_id,
+        &claims,
+        policy::OFFICE_RESTORE,
+        &office_id,
+        &resource,
+        audit::changes(Some(&old_job), Some(&job)),
+    )
+    .await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(job),
+        extra: None::<Empty>,
+    }))
+}
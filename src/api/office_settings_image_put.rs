@@ -0,0 +1,65 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::{Claims, OfficeSettings};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::{OFFICE_IMAGE_STORAGE_CONTAINER, OFFICE_SETTINGS_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{get, upload_blob, upsert};
+use warp::filters::multipart::FormData;
+use warp::reject;
+
+/// Uploads the office's `logo` or `hero` image. The office must have settings.
+pub async fn office_settings_image_put(
+    office_id: String,
+    image: String,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+    f: FormData,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let resource = Resource::office(&office_id);
+    policy::authorize(&claims, policy::OFFICE_SETTINGS_PUT, &resource)?;
+    if image != "logo" && image != "hero" {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "Office settings have no image {}",
+            image
+        ))));
+    }
+
+    let (old_settings, etag): (OfficeSettings, _) =
+        get(OFFICE_SETTINGS_COLLECTION, [&office_id], &office_id).await?;
+    let mut settings = old_settings.clone();
+
+    let image_id = upload_blob(f, "image", "image", OFFICE_IMAGE_STORAGE_CONTAINER).await?;
+    if image == "logo" {
+        settings.logo_image = Some(image_id);
+    } else {
+        settings.hero_image = Some(image_id);
+    }
+    settings.modified = Utc::now();
+
+    upsert(
+        OFFICE_SETTINGS_COLLECTION,
+        [&office_id],
+        &settings,
+        Some(&etag),
+    )
+    .await?;
+
+    audit::record(
+        &request_id,
+        &claims,
+        policy::OFFICE_SETTINGS_PUT,
+        &settings.id,
+        &resource,
+        audit::changes(Some(&old_settings), Some(&settings)),
+    )
+    .await;
+
+    // TODO: Delete old image, if any.
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(settings),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,153 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::{Claims, Office, OfficeSettings};
+use crate::policy::{self, Resource};
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::{OFFICE_COLLECTION, OFFICE_SETTINGS_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{get, upsert, CosmosErrorKind};
+use url::Url;
+use warp::reject;
+
+/// Sets the office's settings, creating them if the office has none yet. The images are kept,
+/// they are set through `office_settings_image_put`.
+pub async fn office_settings_put(
+    office_id: String,
+    r: DataRequest<OfficeSettings, Empty>,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let mut settings = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+
+    let resource = Resource::office(&office_id);
+    policy::authorize(&claims, policy::OFFICE_SETTINGS_PUT, &resource)?;
+    validate(&settings)?;
+
+    let (office, _): (Office, _) = get(OFFICE_COLLECTION, [&office_id], &office_id).await?;
+    if office.deleted {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "Office {} is deleted",
+            office_id
+        ))));
+    }
+    let (old_settings, etag) =
+        match get::<OfficeSettings, _, _, _>(OFFICE_SETTINGS_COLLECTION, [&office_id], &office_id)
+            .await
+        {
+            Ok((s, etag)) => (Some(s), Some(etag)),
+            Err(e) => match e.kind {
+                CosmosErrorKind::NotFound => (None, None),
+                _ => return Err(e.into()),
+            },
+        };
+
+    settings.id = office_id.clone();
+    settings.office_id = office_id.clone();
+    settings.logo_image = old_settings.as_ref().and_then(|s| s.logo_image.clone());
+    settings.hero_image = old_settings.as_ref().and_then(|s| s.hero_image.clone());
+    settings.modified = Utc::now();
+    upsert(
+        OFFICE_SETTINGS_COLLECTION,
+        [&office_id],
+        &settings,
+        etag.as_deref(),
+    )
+    .await?;
+
+    audit::record(
+        &request_id,
+        &claims,
+        policy::OFFICE_SETTINGS_PUT,
+        &settings.id,
+        &resource,
+        audit::changes(old_settings.as_ref(), Some(&settings)),
+    )
+    .await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(settings),
+        extra: None::<Empty>,
+    }))
+}
+
+fn validate(settings: &OfficeSettings) -> Result<(), warp::Rejection> {
+    if let Some(language) = &settings.default_language {
+        if !settings.supported_languages.contains(language) {
+            return Err(reject::custom(Fault::IllegalArgument(format!(
+                "Default language {} is not supported",
+                language
+            ))));
+        }
+    }
+    for colour in &settings.accent_colours {
+        let hex = colour.strip_prefix('#').unwrap_or_default();
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(reject::custom(Fault::IllegalArgument(format!(
+                "Accent colour {} is not of the form #rrggbb",
+                colour
+            ))));
+        }
+    }
+    if let Some(email) = &settings.support_email {
+        if !email.contains('@') {
+            return Err(reject::custom(Fault::IllegalArgument(format!(
+                "Support email {} is not an email",
+                email
+            ))));
+        }
+    }
+    for url in [&settings.terms_url, &settings.privacy_policy_url]
+        .iter()
+        .filter_map(|u| u.as_ref())
+    {
+        if !Url::parse(url).is_ok_and(|u| u.scheme() == "https") {
+            return Err(reject::custom(Fault::IllegalArgument(format!(
+                "{} is not an https url",
+                url
+            ))));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate;
+    use serde_json::json;
+
+    fn valid(settings: serde_json::Value) -> bool {
+        validate(&serde_json::from_value(settings).unwrap()).is_ok()
+    }
+
+    #[test]
+    fn validate_accepts_complete_settings() {
+        assert!(valid(json!({})));
+        assert!(valid(json!({
+            "defaultLanguage": "sv-SE",
+            "supportedLanguages": ["sv-SE", "en-GB"],
+            "accentColours": ["#1a2B3c"],
+            "supportEmail": "support@example.com",
+            "termsUrl": "https://example.com/terms",
+            "privacyPolicyUrl": "https://example.com/privacy",
+        })));
+    }
+
+    #[test]
+    fn validate_rejects_malformed_settings() {
+        assert!(!valid(json!({
+            "defaultLanguage": "sv-SE",
+            "supportedLanguages": ["en-GB"],
+        })));
+        assert!(!valid(json!({ "accentColours": ["1a2b3c"] })));
+        assert!(!valid(json!({ "accentColours": ["#1a2b3"] })));
+        assert!(!valid(json!({ "accentColours": ["#1a2b3g"] })));
+        assert!(!valid(json!({ "supportEmail": "support" })));
+        assert!(!valid(json!({ "termsUrl": "http://example.com/terms" })));
+        assert!(!valid(json!({ "privacyPolicyUrl": "example.com/privacy" })));
+    }
+}
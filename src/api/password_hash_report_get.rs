@@ -0,0 +1,62 @@
+use crate::models::Claims;
+use crate::password;
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::AUTH_EMAIL_COLLECTION;
+use cosmos_utils::query_crosspartition;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    // Accounts hashed with the configured parameters.
+    pub current: u64,
+    // Accounts that will be rehashed on their next signin.
+    pub legacy: u64,
+    // Accounts that only sign in through an identity provider.
+    pub without_password: u64,
+    // Legacy accounts by algorithm and parameters, e.g. "$argon2i$v=19$m=4096,t=3,p=1".
+    pub legacy_params: BTreeMap<String, u64>,
+}
+
+/// Reports how many accounts still have password hashes made with legacy parameters.
+pub async fn password_hash_report_get(
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(&claims, policy::SECURITY_REPORTS_GET, &Resource::none())?;
+
+    let q = format!("SELECT VALUE a.passhash FROM {} a", AUTH_EMAIL_COLLECTION);
+    let hashes: Vec<String> =
+        query_crosspartition(AUTH_EMAIL_COLLECTION, [&()], q, -1, true).await?;
+
+    let mut report = Report::default();
+    for hash in &hashes {
+        if hash.is_empty() {
+            report.without_password += 1;
+        } else if password::needs_rehash(hash) {
+            report.legacy += 1;
+            *report.legacy_params.entry(params_of(hash)).or_insert(0) += 1;
+        } else {
+            report.current += 1;
+        }
+    }
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&report),
+        extra: None::<Empty>,
+    }))
+}
+
+/// The algorithm and parameters of an encoded hash, i.e. everything before the salt.
+fn params_of(hash: &str) -> String {
+    let mut params = vec![];
+    for part in hash.split('$') {
+        params.push(part);
+        if part.starts_with("m=") {
+            break;
+        }
+    }
+    params.join("$")
+}
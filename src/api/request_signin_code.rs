@@ -0,0 +1,87 @@
+use crate::email;
+use crate::fault::Fault;
+use crate::models::{AuthEmail, SigninCode};
+use crate::util::{self, log, DataRequest, DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, SIGNIN_CODE_COLLECTION};
+use chrono::{Duration, Utc};
+use cosmos_utils::{get, upsert, CosmosErrorKind};
+use warp::reject;
+
+/// How long the link and the code can be used.
+const CODE_LIFETIME_MINUTES: i64 = 15;
+
+/// Minimum time between two signin emails to the same address.
+const RESEND_INTERVAL_SECONDS: i64 = 60;
+
+/// Number of digits in the code.
+const CODE_DIGITS: usize = 6;
+
+/// Emails a signin link and code to the address, to be exchanged at `signin_code`. Works for
+/// accounts without a password. The response is the same whether or not the address belongs to a
+/// user, so that it can not be used to find out who has an account.
+pub async fn request_signin_code(
+    r: DataRequest<String, Empty>,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let email = if let Some(q) = r.data {
+        // Normalise email.
+        q.to_lowercase()
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+
+    let response = warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    });
+
+    let auth_email: AuthEmail = match get(AUTH_EMAIL_COLLECTION, [&email], &email).await {
+        Ok((auth_email, _)) => auth_email,
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => return Ok(response),
+            _ => return Err(e.into()),
+        },
+    };
+
+    let id = SigninCode::id_for(&email);
+    let now = Utc::now();
+    match get(SIGNIN_CODE_COLLECTION, [&id], &id).await {
+        Ok((pending, _)) => {
+            let pending: SigninCode = pending;
+            if now < pending.created + Duration::seconds(RESEND_INTERVAL_SECONDS) {
+                return Ok(response);
+            }
+        }
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => (),
+            _ => return Err(e.into()),
+        },
+    }
+
+    // A new request replaces the pending one, so only the latest link and code work.
+    let link_token = util::random_string(32);
+    let code = util::random_digit_string(CODE_DIGITS);
+    let signin_code = SigninCode {
+        id,
+        link_hash: util::hash_token(&link_token),
+        code_hash: SigninCode::hash_code(&email, &code),
+        email: auth_email.id,
+        user_id: auth_email.user_id,
+        attempts: 0,
+        expires: now + Duration::minutes(CODE_LIFETIME_MINUTES),
+        created: now,
+    };
+    upsert(
+        SIGNIN_CODE_COLLECTION,
+        [&signin_code.id],
+        &signin_code,
+        None,
+    )
+    .await?;
+
+    if let Err(e) = email::send_signin_link(&email, &link_token, &code).await {
+        log(format!("Could not send signin link email due to {:?}", e));
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,51 @@
+use crate::email;
+use crate::fault::Fault;
+use crate::models::{Claims, User};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::USER_COLLECTION;
+use chrono::{Duration, Utc};
+use cosmos_utils::{get, upsert};
+use warp::reject;
+
+/// Minimum time between two verification emails to the same user.
+const RESEND_INTERVAL_MINUTES: i64 = 5;
+
+pub async fn resend_verification_email(
+    user_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::USER_CREDENTIALS_PUT,
+        &Resource::owner(&user_id),
+    )?;
+
+    let (mut user, etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+
+    if user.email_verified_at.is_some() {
+        return Err(reject::custom(Fault::IllegalState(String::from(
+            "Email is already verified",
+        ))));
+    }
+
+    let now = Utc::now();
+    if let Some(sent_at) = user.email_verification_sent_at {
+        if now < sent_at + Duration::minutes(RESEND_INTERVAL_MINUTES) {
+            return Err(reject::custom(Fault::Throttling));
+        }
+    }
+
+    // Stored before sending, so that concurrent requests are throttled by the etag.
+    user.email_verification_sent_at = Some(now);
+    user.modified = now;
+    upsert(USER_COLLECTION, [&user_id], &user, Some(&etag)).await?;
+
+    email::send_verification(&user).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
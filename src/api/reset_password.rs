@@ -0,0 +1,98 @@
+use crate::fault::Fault;
+use crate::models::{AuthEmail, PasswordReset, User};
+use crate::password;
+use crate::token;
+use crate::util::{self, log, DataRequest, DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, PASSWORD_RESET_COLLECTION, USER_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{delete, get, query_crosspartition_etag, upsert};
+use warp::reject;
+
+/// Sets a new password given the reset token from a forgot password email. The token can only be
+/// used once, and all of the user's sessions are signed out.
+pub async fn reset_password(
+    r: DataRequest<String, String>,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let reset_token;
+    if let Some(q) = r.data {
+        reset_token = q;
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    }
+    let password;
+    if let Some(q) = r.extra {
+        password = q;
+    } else {
+        return Err(reject::custom(Fault::NoExtra));
+    }
+
+    let reset_id = util::hash_token(&reset_token);
+    let (reset, etag): (PasswordReset, _) = get(PASSWORD_RESET_COLLECTION, [&reset_id], &reset_id)
+        .await
+        .map_err(|_| reject::custom(Fault::Unauthorized))?;
+
+    if reset.expires < Utc::now() {
+        return Err(reject::custom(Fault::Unauthorized));
+    }
+
+    // Checked before the token is used up, so that the user can try another password.
+    let (user, _): (User, _) = get(USER_COLLECTION, [&reset.user_id], &reset.user_id).await?;
+    password::check(&password, &user)?;
+
+    // Deleting with the etag makes sure only one request gets to use the token.
+    delete(
+        PASSWORD_RESET_COLLECTION,
+        [&reset_id],
+        &reset_id,
+        Some(etag),
+    )
+    .await
+    .map_err(|_| reject::custom(Fault::Unauthorized))?;
+
+    let (mut auth_email, etag): (AuthEmail, _) =
+        get(AUTH_EMAIL_COLLECTION, [&reset.email], &reset.email).await?;
+    // The email may have moved to another user since the reset was requested.
+    if auth_email.user_id != reset.user_id {
+        return Err(reject::custom(Fault::Unauthorized));
+    }
+    auth_email.passhash = password::hash(&password)?;
+    upsert(
+        AUTH_EMAIL_COLLECTION,
+        [&auth_email.id],
+        &auth_email,
+        Some(&etag),
+    )
+    .await?;
+
+    // The old password no longer works, so neither should any session started with it.
+    token::revoke_all(&reset.user_id).await?;
+
+    // Any other reset requested by the user is no longer needed.
+    let q = format!(
+        "SELECT * FROM {} r WHERE r.userId = '{}'",
+        PASSWORD_RESET_COLLECTION, reset.user_id
+    );
+    let resets: Vec<(PasswordReset, _)> =
+        query_crosspartition_etag(PASSWORD_RESET_COLLECTION, [&()], q, -1, true).await?;
+    for (other, etag) in resets {
+        if let Err(e) = delete(
+            PASSWORD_RESET_COLLECTION,
+            [&other.id],
+            &other.id,
+            Some(etag),
+        )
+        .await
+        {
+            log(format!(
+                "Could not delete password reset {} due to {}",
+                other.id, e
+            ));
+        }
+    }
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
@@ -1,4 +1,5 @@
 use crate::models::{Claims, Series, SeriesUserData};
+use crate::policy::{self, Resource};
 use crate::util::{DataRequest, Empty};
 use crate::{SERIES_COLLECTION, SERIES_USER_DATA_COLLECTION};
 use cosmos_utils::{get, CosmosErrorKind};
@@ -46,14 +47,11 @@ impl SeriesUserData {
             },
         };
 
-        if claims.sub != user_id {
-            return Err(warp::reject::custom(crate::fault::Fault::Forbidden(
-                format!(
-                    "Calling user does not have the privilege, {} != {}",
-                    claims.sub, user_id
-                ),
-            )));
-        }
+        policy::authorize(
+            &claims,
+            policy::SERIES_USER_DATA_POST,
+            &Resource::owner(&user_id),
+        )?;
 
         // NOTE: We make the id for this the concatenation of the office-id to the series-id with
         // a + between
@@ -0,0 +1,33 @@
+use crate::models::{Claims, ServiceAccount};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::SERVICE_ACCOUNT_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::modify;
+
+/// Revokes a service account, its API key stops working immediately. The account is kept for
+/// reference.
+pub async fn service_account_delete(
+    account_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(&claims, policy::SERVICE_ACCOUNTS_MANAGE, &Resource::none())?;
+
+    modify(
+        SERVICE_ACCOUNT_COLLECTION,
+        [&account_id],
+        &account_id,
+        |mut account: ServiceAccount| {
+            account.revoked = true;
+            account.modified = Utc::now();
+            Ok(account)
+        },
+    )
+    .await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
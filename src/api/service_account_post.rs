@@ -0,0 +1,54 @@
+use crate::fault::Fault;
+use crate::models::{Claims, Role};
+use crate::policy::{self, Resource};
+use crate::service_account;
+use crate::util::{DataRequest, DataResponse, Empty};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use warp::reject;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountData {
+    name: String,
+    #[serde(default)]
+    roles: Vec<Role>,
+    #[serde(default)]
+    expires: Option<DateTime<Utc>>,
+}
+
+/// Creates a service account. The API key is returned as extra and is only shown this once.
+pub async fn service_account_post(
+    r: DataRequest<ServiceAccountData, Empty>,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let data = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+
+    policy::authorize(&claims, policy::SERVICE_ACCOUNTS_MANAGE, &Resource::none())?;
+
+    if data.name.trim().is_empty() {
+        return Err(reject::custom(Fault::IllegalArgument(String::from(
+            "A service account needs a name",
+        ))));
+    }
+    if let Some(expires) = data.expires {
+        if expires <= Utc::now() {
+            return Err(reject::custom(Fault::IllegalArgument(String::from(
+                "Expiry is in the past",
+            ))));
+        }
+    }
+
+    let (account, key) =
+        service_account::create(data.name, data.roles, data.expires, &claims.sub).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&account),
+        extra: Some(&key),
+    }))
+}
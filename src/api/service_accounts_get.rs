@@ -0,0 +1,28 @@
+use crate::models::{Claims, ServiceAccount};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::SERVICE_ACCOUNT_COLLECTION;
+use cosmos_utils::query_crosspartition;
+
+/// Lists all service accounts, including revoked and expired ones.
+pub async fn service_accounts_get(
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(&claims, policy::SERVICE_ACCOUNTS_MANAGE, &Resource::none())?;
+
+    let q = format!(
+        "SELECT * FROM {} a ORDER BY a.created DESC",
+        SERVICE_ACCOUNT_COLLECTION
+    );
+    let mut accounts: Vec<ServiceAccount> =
+        query_crosspartition(SERVICE_ACCOUNT_COLLECTION, [&()], q, -1, true).await?;
+    for account in accounts.iter_mut() {
+        account.key_hash = String::new();
+    }
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&accounts),
+        extra: None::<Empty>,
+    }))
+}
@@ -1,13 +1,21 @@
 use crate::fault::Fault;
-use crate::models::{AuthEmail, Claims, User};
-use crate::util::{self, DataRequest, DataResponse, Empty};
-use crate::{ACCESS_TOKEN_SECRET, AUTH_EMAIL_COLLECTION, REFRESH_TOKEN_SECRET, USER_COLLECTION};
-use chrono::{prelude::*, Duration};
-use cosmos_utils::get;
-use jsonwebtoken::{encode, EncodingKey, Header}; // decode, Validation, DecodingKey, Algorithm, errors::ErrorKind
+use crate::models::{AuthEmail, User};
+use crate::password;
+use crate::throttle::{self, Subject};
+use crate::two_factor::{self, Signin};
+use crate::util::{log, DataRequest, DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, USER_COLLECTION};
+use cosmos_utils::{get, upsert, CosmosErrorKind};
+use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use warp::reject;
 
+lazy_static! {
+    // Verified against when the email is unknown, so that it takes as long as a wrong password.
+    static ref DUMMY_PASSHASH: String =
+        password::hash("dummy password").expect("Could not hash dummy password");
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum SigninType {
@@ -18,14 +26,21 @@ pub enum SigninType {
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Response<'a> {
-    pub access_token: &'a str,
-    pub refresh_token: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<&'a str>,
+    // Set instead of the tokens when the user has two factor authentication enabled, it is
+    // answered with a code at `signin_two_factor`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub two_factor_token: Option<&'a str>,
     pub user_id: &'a str,
 }
 
 pub async fn signin(
     // Only accept email and password here
     r: DataRequest<String, String>,
+    ip: String,
     _v: u8,
 ) -> Result<impl warp::Reply, warp::Rejection> {
     let email;
@@ -43,54 +58,78 @@ pub async fn signin(
     } else {
         return Err(reject::custom(Fault::NoExtra));
     }
-    let (auth_email, _): (AuthEmail, _) = get(AUTH_EMAIL_COLLECTION, [&email], email.clone())
-        .await
-        .map_err(|_| reject::custom(Fault::NotFound(format!("Could not find email"))))?;
-    if !util::verify_hash(&auth_email.passhash, password.as_bytes()) {
-        return Err(reject::custom(Fault::WrongPassword));
-    }
-    let user_id = auth_email.user_id;
 
-    let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
-
-    let iat = Utc::now();
-    let exp = iat + Duration::minutes(20);
-    let claims = Claims::new(&user_id, exp, &user.roles.clone());
+    throttle::check(Subject::Ip(&ip)).await?;
+    throttle::check(Subject::Email(&email)).await?;
 
-    let access_token = match encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(ACCESS_TOKEN_SECRET.as_ref()),
-    ) {
-        Ok(token) => token,
-        Err(error) => {
-            return Err(reject::custom(Fault::Unspecified(format!(
-                "Could not encode access token: {}.",
-                error.to_string()
-            ))));
+    // NOTE: Unknown emails and wrong passwords must be indistinguishable, or the endpoint can be
+    // used to find out who has an account.
+    let auth_email: Option<(AuthEmail, String)> =
+        match get(AUTH_EMAIL_COLLECTION, [&email], email.clone()).await {
+            Ok(auth_email) => Some(auth_email),
+            Err(e) => match e.kind {
+                CosmosErrorKind::NotFound => None,
+                _ => return Err(e.into()),
+            },
+        };
+    let passhash = match &auth_email {
+        Some((auth_email, _)) => &auth_email.passhash,
+        None => &*DUMMY_PASSHASH,
+    };
+    let verified = password::verify(passhash, &password)?;
+    let (mut auth_email, etag) = match auth_email {
+        Some(auth_email) if verified => auth_email,
+        _ => {
+            throttle::record_failure(Subject::Ip(&ip)).await?;
+            throttle::record_failure(Subject::Email(&email)).await?;
+            return Err(reject::custom(Fault::WrongPassword));
         }
     };
 
-    let refresh_token = match encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(REFRESH_TOKEN_SECRET.as_ref()),
-    ) {
-        Ok(token) => token,
-        Err(error) => {
-            return Err(reject::custom(Fault::Unspecified(format!(
-                "Could not encode refresh token: {}.",
-                error.to_string()
-            ))));
+    // The password is only ever in hand here, so hashes made with older parameters are upgraded.
+    if password::needs_rehash(&auth_email.passhash) {
+        auth_email.passhash = password::hash(&password)?;
+        if let Err(e) = upsert(
+            AUTH_EMAIL_COLLECTION,
+            [&auth_email.id],
+            &auth_email,
+            Some(&etag),
+        )
+        .await
+        {
+            log(format!(
+                "Could not rehash password of {} due to {}",
+                auth_email.id, e
+            ));
         }
+    }
+    let user_id = auth_email.user_id.clone();
+
+    let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+
+    let signin = two_factor::begin_signin(&user, Some(&auth_email), &ip).await?;
+    // Failures are only forgotten once a session is issued. Answering the second factor counts
+    // against the same email, so signing in again must not reset its failed codes.
+    if let Signin::Session(_) = &signin {
+        throttle::clear(Subject::Email(&email)).await?;
+    }
+    let response = match &signin {
+        Signin::Session(tokens) => Response {
+            access_token: Some(&tokens.access_token),
+            refresh_token: Some(&tokens.refresh_token),
+            two_factor_token: None,
+            user_id: &user_id,
+        },
+        Signin::Challenge(challenge) => Response {
+            access_token: None,
+            refresh_token: None,
+            two_factor_token: Some(challenge),
+            user_id: &user_id,
+        },
     };
 
     Ok(warp::reply::json(&DataResponse {
-        data: Some(&Response {
-            access_token: &access_token,
-            refresh_token: &refresh_token,
-            user_id: &user_id,
-        }),
+        data: Some(&response),
         extra: None::<Empty>,
     }))
 }
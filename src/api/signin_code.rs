@@ -0,0 +1,108 @@
+use super::signin::Response;
+use crate::fault::Fault;
+use crate::models::{AuthEmail, SigninCode, User};
+use crate::throttle::{self, Subject};
+use crate::two_factor::{self, Signin};
+use crate::util::{self, DataRequest, DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, SIGNIN_CODE_COLLECTION, USER_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{delete, get, upsert, CosmosErrorKind};
+use warp::reject;
+
+/// Wrong codes allowed before the pending signin is dropped and a new code must be requested.
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Signs in with the token from a signin link or the code from the same email, see
+/// `request_signin_code`. Returns the same as `signin`.
+pub async fn signin_code(
+    r: DataRequest<String, String>,
+    ip: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let email = if let Some(q) = r.data {
+        // Normalise email.
+        q.to_lowercase()
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+    let secret = if let Some(q) = r.extra {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoExtra));
+    };
+
+    throttle::check(Subject::Ip(&ip)).await?;
+    throttle::check(Subject::Email(&email)).await?;
+
+    let id = SigninCode::id_for(&email);
+    let pending: Option<(SigninCode, String)> = match get(SIGNIN_CODE_COLLECTION, [&id], &id).await
+    {
+        Ok(pending) => Some(pending),
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => None,
+            _ => return Err(e.into()),
+        },
+    };
+    let (mut pending, etag) = match pending {
+        Some((pending, etag)) if pending.expires > Utc::now() => (pending, etag),
+        _ => return Err(reject::custom(Fault::Unauthorized)),
+    };
+
+    let accepted = util::hash_token(&secret) == pending.link_hash
+        || SigninCode::hash_code(&email, secret.trim()) == pending.code_hash;
+    if !accepted {
+        pending.attempts += 1;
+        if pending.attempts >= MAX_ATTEMPTS {
+            delete(SIGNIN_CODE_COLLECTION, [&id], &id, Some(etag)).await?;
+        } else {
+            upsert(SIGNIN_CODE_COLLECTION, [&id], &pending, Some(&etag)).await?;
+        }
+        throttle::record_failure(Subject::Ip(&ip)).await?;
+        throttle::record_failure(Subject::Email(&email)).await?;
+        return Err(reject::custom(Fault::WrongPassword));
+    }
+
+    // Deleting with the etag makes the code single use, also under concurrent requests.
+    if let Err(e) = delete(SIGNIN_CODE_COLLECTION, [&id], &id, Some(etag)).await {
+        return match e.kind {
+            CosmosErrorKind::PreconditionFailed | CosmosErrorKind::NotFound => {
+                Err(reject::custom(Fault::Unauthorized))
+            }
+            _ => Err(e.into()),
+        };
+    }
+
+    let (auth_email, _): (AuthEmail, _) =
+        get(AUTH_EMAIL_COLLECTION, [&pending.email], &pending.email).await?;
+    let (user, _etag): (User, _) =
+        get(USER_COLLECTION, [&pending.user_id], &pending.user_id).await?;
+    if user.deleted || auth_email.user_id != user.id {
+        return Err(reject::custom(Fault::Unauthorized));
+    }
+
+    let signin = two_factor::begin_signin(&user, Some(&auth_email), &ip).await?;
+    // Failures are only forgotten once a session is issued. Answering the second factor counts
+    // against the same email, so signing in again must not reset its failed codes.
+    if let Signin::Session(_) = &signin {
+        throttle::clear(Subject::Email(&email)).await?;
+    }
+    let response = match &signin {
+        Signin::Session(tokens) => Response {
+            access_token: Some(&tokens.access_token),
+            refresh_token: Some(&tokens.refresh_token),
+            two_factor_token: None,
+            user_id: &user.id,
+        },
+        Signin::Challenge(challenge) => Response {
+            access_token: None,
+            refresh_token: None,
+            two_factor_token: Some(challenge),
+            user_id: &user.id,
+        },
+    };
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&response),
+        extra: None::<Empty>,
+    }))
+}
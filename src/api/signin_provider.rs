@@ -0,0 +1,91 @@
+use super::signin::Response;
+use crate::fault::Fault;
+use crate::guest;
+use crate::identity::{self, Provider};
+use crate::models::{AuthEmail, Claims, User};
+use crate::two_factor::{self, Signin};
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, USER_COLLECTION};
+use cosmos_utils::{get, CosmosErrorKind};
+use serde::Deserialize;
+use warp::reject;
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderSigninData {
+    nonce: Option<String>,
+    // Used for new users when the provider's token carries no name, e.g. Apple only shares the
+    // user's name with the app and only on the first signin.
+    first_name: Option<String>,
+    last_name: Option<String>,
+}
+
+/// Signs in with a credential from an external identity provider, see `Provider::verify`. Called
+/// with a guest's token a new identity upgrades the guest in place, an identity of an existing
+/// user signs in as that user and leaves the guest to be cleaned up.
+pub async fn signin_provider(
+    provider: String,
+    r: DataRequest<String, ProviderSigninData>,
+    claims: Option<Claims>,
+    ip: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let credential = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+    let signin_data = r.extra.unwrap_or_default();
+
+    let provider = Provider::find(&provider).ok_or_else(|| {
+        reject::custom(Fault::NotFound(format!(
+            "Unknown identity provider {}",
+            provider
+        )))
+    })?;
+    let mut external = provider
+        .verify(&credential, signin_data.nonce.as_deref())
+        .await?;
+    external.first_name = external.first_name.or(signin_data.first_name);
+    external.last_name = external.last_name.or(signin_data.last_name);
+
+    let guest = guest::find(claims.as_ref()).await?;
+    let user_id = identity::resolve(&external, guest).await?;
+
+    let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+    if user.deleted {
+        return Err(reject::custom(Fault::Forbidden(String::from(
+            "User is deleted",
+        ))));
+    }
+
+    // The provider only stands in for the password, a second factor is still required.
+    let email = user.email.to_lowercase();
+    let auth_email: Option<AuthEmail> = match get(AUTH_EMAIL_COLLECTION, [&email], &email).await {
+        Ok((auth_email, _)) => Some(auth_email),
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => None,
+            _ => return Err(e.into()),
+        },
+    };
+    let signin = two_factor::begin_signin(&user, auth_email.as_ref(), &ip).await?;
+    let response = match &signin {
+        Signin::Session(tokens) => Response {
+            access_token: Some(&tokens.access_token),
+            refresh_token: Some(&tokens.refresh_token),
+            two_factor_token: None,
+            user_id: &user_id,
+        },
+        Signin::Challenge(challenge) => Response {
+            access_token: None,
+            refresh_token: None,
+            two_factor_token: Some(challenge),
+            user_id: &user_id,
+        },
+    };
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&response),
+        extra: None::<Empty>,
+    }))
+}
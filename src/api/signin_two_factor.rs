@@ -0,0 +1,85 @@
+use crate::fault::Fault;
+use crate::models::{AuthEmail, TokenPurpose, User};
+use crate::throttle::{self, Subject};
+use crate::token;
+use crate::two_factor;
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, USER_COLLECTION};
+use cosmos_utils::{get, upsert};
+use serde::Serialize;
+use warp::reject;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response<'a> {
+    pub access_token: &'a str,
+    pub refresh_token: &'a str,
+    pub user_id: &'a str,
+}
+
+/// Completes a signin by answering the two factor token from `signin` or `signin_provider` with a
+/// code from the user's authenticator or one of their recovery codes.
+pub async fn signin_two_factor(
+    r: DataRequest<String, String>,
+    ip: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let challenge = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+    let code = if let Some(q) = r.extra {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoExtra));
+    };
+
+    let claims = token::decode_purpose_token(&challenge, TokenPurpose::TwoFactor)?;
+    let email = claims
+        .eml
+        .ok_or_else(|| reject::custom(Fault::Unauthorized))?;
+
+    throttle::check(Subject::Ip(&ip)).await?;
+    throttle::check(Subject::Email(&email)).await?;
+
+    let (mut auth_email, etag): (AuthEmail, _) =
+        get(AUTH_EMAIL_COLLECTION, [&email], &email).await?;
+    if auth_email.user_id != claims.sub {
+        return Err(reject::custom(Fault::Unauthorized));
+    }
+    let accepted = match &mut auth_email.two_factor {
+        Some(two_factor) if two_factor.confirmed => two_factor::verify(two_factor, &code)?,
+        _ => {
+            return Err(reject::custom(Fault::IllegalState(String::from(
+                "Two factor authentication is not enabled",
+            ))))
+        }
+    };
+    if !accepted {
+        throttle::record_failure(Subject::Ip(&ip)).await?;
+        throttle::record_failure(Subject::Email(&email)).await?;
+        return Err(reject::custom(Fault::WrongPassword));
+    }
+    // Stores the used up code, the etag makes sure a code is only accepted once.
+    upsert(
+        AUTH_EMAIL_COLLECTION,
+        [&auth_email.id],
+        &auth_email,
+        Some(&etag),
+    )
+    .await?;
+    throttle::clear(Subject::Email(&email)).await?;
+
+    let (user, _etag): (User, _) = get(USER_COLLECTION, [&claims.sub], &claims.sub).await?;
+    let tokens = token::issue(&user, true, &ip).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&Response {
+            access_token: &tokens.access_token,
+            refresh_token: &tokens.refresh_token,
+            user_id: &user.id,
+        }),
+        extra: None::<Empty>,
+    }))
+}
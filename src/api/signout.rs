@@ -0,0 +1,24 @@
+use crate::models::Claims;
+use crate::policy::{self, Resource};
+use crate::token;
+use crate::util::{DataResponse, Empty};
+
+/// Signs out the session the calling access token belongs to. The session can no longer be
+/// refreshed and the access token expires on its own shortly.
+pub async fn signout(
+    user_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(&claims, policy::USER_SIGNOUT, &Resource::owner(&user_id))?;
+
+    // Tokens issued before sessions were tracked have nothing to revoke.
+    if let Some(family_id) = &claims.fam {
+        token::revoke(&user_id, family_id).await?;
+    }
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,20 @@
+use crate::models::Claims;
+use crate::policy::{self, Resource};
+use crate::token;
+use crate::util::{DataResponse, Empty};
+
+/// Signs out every session of the user, including the calling one.
+pub async fn signout_all(
+    user_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(&claims, policy::USER_SIGNOUT, &Resource::owner(&user_id))?;
+
+    let _user = token::revoke_all(&user_id).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
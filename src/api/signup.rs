@@ -1,15 +1,15 @@
 use crate::{
+    email,
     fault::Fault,
+    guest,
     models::{AuthEmail, Claims, User},
-    util::{self, DataRequest, DataResponse, Empty},
-    ACCESS_TOKEN_SECRET, APPLICATION_INSIGHTS_INSTRUMENTATION_KEY, AUTH_EMAIL_COLLECTION,
-    DEFAULT_OFFICE_ID, PRODUCTION_ENVIRONMENT, REFRESH_TOKEN_SECRET, SENDGRID_API_KEY,
-    USER_COLLECTION,
+    password, token,
+    util::{log, DataRequest, DataResponse, Empty},
+    APPLICATION_INSIGHTS_INSTRUMENTATION_KEY, AUTH_EMAIL_COLLECTION, DEFAULT_OFFICE_ID,
+    PRODUCTION_ENVIRONMENT, SENDGRID_API_KEY, USER_COLLECTION,
 };
 use appinsights::TelemetryClient;
-use chrono::{prelude::*, Duration};
-use cosmos_utils::{delete, insert};
-use jsonwebtoken::{encode, EncodingKey, Header};
+use cosmos_utils::{delete, insert, upsert};
 use sendgrid::v3::*;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -27,11 +27,14 @@ pub struct Response<'a> {
 #[serde(rename_all = "camelCase")]
 pub struct SignupData {
     password: Option<String>,
-    //apple_authentication_code: Option<String>,
 }
 
+/// Signs up with email and password. Called with a guest's token the guest is upgraded in place,
+/// keeping its user id and with it its listening data.
 pub async fn signup(
     r: DataRequest<User, SignupData>,
+    claims: Option<Claims>,
+    ip: String,
     _v: u8,
 ) -> Result<impl warp::Reply, warp::Rejection> {
     let mut user = if let Some(q) = r.data {
@@ -45,27 +48,38 @@ pub async fn signup(
         return Err(reject::custom(Fault::NoExtra));
     };
     let password;
-    //if signup_data.apple_authentication_code.is_some() {
-    //    let authentication_code = signup_data.apple_authentication_code.unwrap();
-    //    let client = Client::new(
-    //        APPLE_SIGNIN_CLIENT_ID.to_string(),
-    //        APPLE_SIGNIN_PRIVATE_KEY.to_string(),
-    //        APPLE_SIGNIN_KID.to_string(),
-    //        APPLE_SIGNIN_TEAM_ID.to_string(),
-    //    )
-    //    .unwrap();
-    //    client.authenticate(authentication_code).await.unwrap();
-    //    unimplemented!();
     if signup_data.password.is_some() {
         password = signup_data.password.unwrap();
     } else {
         return Err(reject::custom(Fault::NoExtra));
     }
 
+    password::check(&password, &user)?;
+
     if user.id == "" {
         user.id = Uuid::new_v4().to_string();
     }
 
+    // Roles are granted by admins, not chosen at signup.
+    user.roles = vec![];
+    user.guest = false;
+    user.guest_device = None;
+    // Offices are joined through join codes and invitations, not chosen at signup.
+    user.office_ids = vec![DEFAULT_OFFICE_ID.to_string()];
+    user.office_joined_at = Default::default();
+
+    let guest = guest::find(claims.as_ref()).await?;
+    if let Some((guest, _)) = &guest {
+        user.id = guest.id.clone();
+        user.office_ids = guest.office_ids.clone();
+        user.office_joined_at = guest.office_joined_at.clone();
+        user.saved_series = guest.saved_series.clone();
+        user.favourite_episode_ids = guest.favourite_episode_ids.clone();
+        user.devices = guest.devices.clone();
+        user.token_generation = guest.token_generation;
+        user.role_generation = guest.role_generation;
+    }
+
     // If no preferred name is given then the preferred name is set to the first name
     if let None = user.preferred_name {
         user.preferred_name = Some(user.first_name.clone());
@@ -83,14 +97,23 @@ pub async fn signup(
         user.test = true;
     }
 
-    user.office_ids = vec![DEFAULT_OFFICE_ID.to_string()];
+    // The email is unverified until the link in the verification email is opened.
+    user.email_verified_at = None;
+    user.email_verification_sent_at = Some(chrono::Utc::now());
+    user.pending_email = None;
 
-    user.created = chrono::Utc::now();
+    user.created = match &guest {
+        Some((guest, _)) => guest.created,
+        None => chrono::Utc::now(),
+    };
 
     // Set at.
     user.modified = chrono::Utc::now();
 
-    let user_etag = insert(USER_COLLECTION, [&user.id], &user, None).await?;
+    let user_etag = match &guest {
+        Some((_, etag)) => upsert(USER_COLLECTION, [&user.id], &user, Some(etag)).await?,
+        None => insert(USER_COLLECTION, [&user.id], &user, None).await?,
+    };
 
     // Normalise email.
     let email = user.email.clone().to_lowercase();
@@ -98,19 +121,36 @@ pub async fn signup(
     // Add email auth.
     let email_auth = AuthEmail {
         id: email,
-        passhash: util::hash(password.as_bytes()), // Calculate pass hash.
+        passhash: password::hash(&password)?,
         user_id: user.id.clone(),
+        two_factor: None,
     };
 
-    // TODO(Jonathan): Should we try to make sure the email is a real email?
     match insert(AUTH_EMAIL_COLLECTION, [&email_auth.id], &email_auth, None).await {
         Ok(_) => (),
         Err(e) => {
-            // NOTE: Error here should hard delete the previously inserted document.
-            delete(USER_COLLECTION, [&user.id], &user.id, Some(user_etag)).await?;
+            match &guest {
+                // The upgrade is undone so the guest keeps working.
+                Some((guest, _)) => {
+                    upsert(USER_COLLECTION, [&user.id], guest, Some(&user_etag)).await?;
+                }
+                // NOTE: Error here should hard delete the previously inserted document.
+                None => {
+                    delete(USER_COLLECTION, [&user.id], &user.id, Some(user_etag)).await?;
+                }
+            }
             return Err(e.into());
         }
     }
+    // The device id was the guest's only credential, so every session of the guest is revoked
+    // and only the session issued below can act as the upgraded user.
+    let user = match &guest {
+        Some((guest, _)) => {
+            guest::forget_device(guest).await;
+            token::revoke_all(&user.id).await?
+        }
+        None => user,
+    };
 
     // Report event.
     let application_insight =
@@ -137,37 +177,11 @@ pub async fn signup(
     //    }
     //};
 
-    let iat = Utc::now();
-    let exp = iat + Duration::minutes(20);
-    let claims = Claims::new(&user.id, exp, &vec![]);
-
-    let access_token = match encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(ACCESS_TOKEN_SECRET.as_ref()),
-    ) {
-        Ok(token) => token,
-        Err(error) => {
-            return Err(reject::custom(Fault::Unspecified(format!(
-                "Could not encode access token: {}.",
-                error.to_string()
-            ))));
-        }
-    };
+    if let Err(e) = email::send_verification(&user).await {
+        log(format!("Could not send verification email due to {:?}", e));
+    }
 
-    let refresh_token = match encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(REFRESH_TOKEN_SECRET.as_ref()),
-    ) {
-        Ok(token) => token,
-        Err(error) => {
-            return Err(reject::custom(Fault::Unspecified(format!(
-                "Could not encode refresh token: {}.",
-                error.to_string()
-            ))));
-        }
-    };
+    let tokens = token::issue(&user, false, &ip).await?;
 
     // Send email.
     let mut map = SGMap::new();
@@ -185,8 +199,8 @@ pub async fn signup(
 
     Ok(warp::reply::json(&DataResponse {
         data: Some(&Response {
-            access_token: &access_token,
-            refresh_token: &refresh_token,
+            access_token: &tokens.access_token,
+            refresh_token: &tokens.refresh_token,
             user_id: &email_auth.user_id,
         }),
         extra: None::<Empty>,
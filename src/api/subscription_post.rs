@@ -1,14 +1,17 @@
 use crate::{
     fault::Fault,
-    models::{Claims, Payment, Subscription, User},
+    models::{Claims, Office, Payment, Subscription, User},
+    policy::{self, Resource},
+    product_catalog,
     util::{DataRequest, DataResponse, Empty},
     APPLICATION_INSIGHTS_TELEMETRY_CLIENT, IN_APP_PURCHASES_APPLE_BUNDLE_ID,
     IN_APP_PURCHASES_APPLE_ISSUER, IN_APP_PURCHASES_APPLE_KEY, IN_APP_PURCHASES_APPLE_KEY_ID,
     IN_APP_PURCHASES_APPLE_PASSWORD, IN_APP_PURCHASES_GOOGLE_KEY,
-    IN_APP_PURCHASES_GOOGLE_SERVICE_ACCOUNT, SUBSCRIPTION_COLLECTION, USER_COLLECTION,
+    IN_APP_PURCHASES_GOOGLE_SERVICE_ACCOUNT, OFFICE_COLLECTION,
+    REQUIRE_VERIFIED_EMAIL_FOR_PURCHASE_SINCE, SUBSCRIPTION_COLLECTION, USER_COLLECTION,
 };
 use appinsights::telemetry::SeverityLevel;
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use cosmos_utils::{get, insert, query};
 use in_app_purchases::Purchase as InAppPurchase;
 use serde::Deserialize;
@@ -19,8 +22,6 @@ use warp::reject;
 pub struct ExtraRequest {
     pub platform: in_app_purchases::Platform,
 
-    pub office_id: String,
-
     // Used by android.
     #[serde(default)]
     pub product_id: Option<String>,
@@ -33,7 +34,7 @@ pub struct ExtraRequest {
 pub async fn subscription_post(
     user_id: String,
     r: DataRequest<String, ExtraRequest>,
-    _claims: Claims,
+    claims: Claims,
     _v: u8,
 ) -> Result<impl warp::Reply, warp::Rejection> {
     let receipt;
@@ -43,20 +44,29 @@ pub async fn subscription_post(
         return Err(reject::custom(Fault::NoData));
     }
 
-    let (platform, office_id, product_id, package_name) = match r.extra {
-        Some(extra) => (
-            extra.platform,
-            extra.office_id,
-            extra.product_id,
-            extra.package_name,
-        ),
+    let (platform, product_id, package_name) = match r.extra {
+        Some(extra) => (extra.platform, extra.product_id, extra.package_name),
         None => {
             return Err(reject::custom(Fault::NoExtra));
         }
     };
 
+    policy::authorize(
+        &claims,
+        policy::SUBSCRIPTION_POST,
+        &Resource::owner(&user_id),
+    )?;
+
     let (user, _): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
 
+    if REQUIRE_VERIFIED_EMAIL_FOR_PURCHASE_SINCE.is_some_and(|since| user.created >= since)
+        && user.email_verified_at.is_none()
+    {
+        return Err(reject::custom(Fault::Ineligible(String::from(
+            "Email must be verified before purchasing a subscription",
+        ))));
+    }
+
     let gateway = match in_app_purchases::Gateway::new(
         IN_APP_PURCHASES_APPLE_BUNDLE_ID.to_string(),
         IN_APP_PURCHASES_APPLE_KEY_ID.to_string(),
@@ -187,6 +197,48 @@ pub async fn subscription_post(
         }
     }
 
+    // The office is the one the catalog maps the purchased product to, never the client's choice.
+    let (purchased_product_id, purchased_package_name) = match &purchase {
+        InAppPurchase::AppleSubscription { product_id, .. } => {
+            (product_id, &*IN_APP_PURCHASES_APPLE_BUNDLE_ID)
+        }
+        InAppPurchase::GoogleSubscription {
+            product_id,
+            package_name,
+            ..
+        } => (product_id, package_name),
+        _ => {
+            return Err(reject::custom(Fault::IllegalState(String::from(
+                "Subscription purchase is not a apple or google subscription",
+            ))));
+        }
+    };
+    let product = match product_catalog::find(
+        platform,
+        purchased_product_id,
+        purchased_package_name,
+    )
+    .await?
+    {
+        Some(product) if product.product_type == in_app_purchases::ProductType::Subscription => {
+            product
+        }
+        _ => {
+            return Err(reject::custom(Fault::Ineligible(format!(
+                "Product {} is not a subscription to any office",
+                purchased_product_id
+            ))));
+        }
+    };
+    let office_id = product.office_id;
+    let (office, _): (Office, _) = get(OFFICE_COLLECTION, [&office_id], &office_id).await?;
+    if office.deleted {
+        return Err(reject::custom(Fault::Ineligible(format!(
+            "Office {} is deleted",
+            office_id
+        ))));
+    }
+
     let payment = match purchase {
         InAppPurchase::AppleSubscription {
             product_id,
@@ -225,10 +277,13 @@ pub async fn subscription_post(
     let mut subscription = Subscription {
         id: uuid::Uuid::new_v4().to_string(),
         deleted: false,
+        office_deleted: false,
         office_id,
         user_id: user_id.clone(),
         start: now,
-        end: None,
+        end: product
+            .entitlement_days
+            .map(|days| now + Duration::days(days)),
         payments: vec![payment],
         created: now,
         modified: now,
@@ -0,0 +1,58 @@
+use crate::fault::Fault;
+use crate::models::{AuthEmail, Claims, User};
+use crate::policy::{self, Resource};
+use crate::throttle::{self, Subject};
+use crate::two_factor;
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, USER_COLLECTION};
+use cosmos_utils::{get, upsert};
+use warp::reject;
+
+/// Enables two factor authentication with a code from the newly set up authenticator. Returns the
+/// recovery codes, which are only shown this once.
+pub async fn two_factor_confirm(
+    user_id: String,
+    r: DataRequest<String, Empty>,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let code = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+    policy::authorize(
+        &claims,
+        policy::USER_CREDENTIALS_PUT,
+        &Resource::owner(&user_id),
+    )?;
+
+    throttle::check(Subject::TwoFactor(&user_id)).await?;
+    let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+    let email = user.email.to_lowercase();
+    let (mut auth_email, etag): (AuthEmail, _) =
+        get(AUTH_EMAIL_COLLECTION, [&email], &email).await?;
+
+    let recovery_codes = match two_factor::confirm(&mut auth_email, &code) {
+        Ok(recovery_codes) => recovery_codes,
+        Err(e) => {
+            if let Some(Fault::WrongPassword) = e.find::<Fault>() {
+                throttle::record_failure(Subject::TwoFactor(&user_id)).await?;
+            }
+            return Err(e);
+        }
+    };
+    upsert(
+        AUTH_EMAIL_COLLECTION,
+        [&auth_email.id],
+        &auth_email,
+        Some(&etag),
+    )
+    .await?;
+    throttle::clear(Subject::TwoFactor(&user_id)).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&recovery_codes),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,62 @@
+use crate::fault::Fault;
+use crate::models::{AuthEmail, Claims, User};
+use crate::policy::{self, Resource};
+use crate::throttle::{self, Subject};
+use crate::two_factor;
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, USER_COLLECTION};
+use cosmos_utils::{get, upsert};
+use warp::reject;
+
+/// Disables two factor authentication. Requires a current code or a recovery code, so that a
+/// stolen session can not turn it off, and failed codes are throttled.
+pub async fn two_factor_disable(
+    user_id: String,
+    r: DataRequest<String, Empty>,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let code = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+    policy::authorize(
+        &claims,
+        policy::USER_CREDENTIALS_PUT,
+        &Resource::owner(&user_id),
+    )?;
+
+    throttle::check(Subject::TwoFactor(&user_id)).await?;
+    let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+    let email = user.email.to_lowercase();
+    let (mut auth_email, etag): (AuthEmail, _) =
+        get(AUTH_EMAIL_COLLECTION, [&email], &email).await?;
+
+    let accepted = match &mut auth_email.two_factor {
+        Some(two_factor) if two_factor.confirmed => two_factor::verify(two_factor, &code)?,
+        _ => {
+            return Err(reject::custom(Fault::IllegalState(String::from(
+                "Two factor authentication is not enabled",
+            ))))
+        }
+    };
+    if !accepted {
+        throttle::record_failure(Subject::TwoFactor(&user_id)).await?;
+        return Err(reject::custom(Fault::WrongPassword));
+    }
+    auth_email.two_factor = None;
+    upsert(
+        AUTH_EMAIL_COLLECTION,
+        [&auth_email.id],
+        &auth_email,
+        Some(&etag),
+    )
+    .await?;
+    throttle::clear(Subject::TwoFactor(&user_id)).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,39 @@
+use crate::models::{AuthEmail, Claims, User};
+use crate::policy::{self, Resource};
+use crate::two_factor;
+use crate::util::{DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, USER_COLLECTION};
+use cosmos_utils::{get, upsert};
+
+/// Starts setting up two factor authentication for the signed in user. The returned secret has to
+/// be confirmed with `two_factor_confirm` before it is required on signin.
+pub async fn two_factor_post(
+    user_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::USER_CREDENTIALS_PUT,
+        &Resource::owner(&user_id),
+    )?;
+
+    let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+    let email = user.email.to_lowercase();
+    let (mut auth_email, etag): (AuthEmail, _) =
+        get(AUTH_EMAIL_COLLECTION, [&email], &email).await?;
+
+    let enrollment = two_factor::enroll(&mut auth_email)?;
+    upsert(
+        AUTH_EMAIL_COLLECTION,
+        [&auth_email.id],
+        &auth_email,
+        Some(&etag),
+    )
+    .await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&enrollment),
+        extra: None::<Empty>,
+    }))
+}
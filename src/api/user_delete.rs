@@ -1,7 +1,10 @@
+use crate::audit::{self, Changes};
 use crate::fault::Fault;
-use crate::models::{Claims, RoleFlags, User};
+use crate::models::{Claims, User};
+use crate::policy::{self, Resource};
+use crate::token;
 use crate::util::SecretKey;
-use crate::util::{encrypt_optional_string, encrypt_string, has_role, log, DataResponse, Empty};
+use crate::util::{encrypt_optional_string, encrypt_string, log, DataResponse, Empty};
 use crate::{AUTH_EMAIL_COLLECTION, USER_COLLECTION};
 use chrono::Utc;
 use cosmos_utils::modify;
@@ -10,26 +13,16 @@ use warp::reject;
 pub async fn user_delete(
     user_id: String,
     claims: Claims,
+    request_id: String,
     _v: u8,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    if claims.sub != user_id
-        && !has_role(
-            None,
-            &claims,
-            RoleFlags::OFFICE_PERSONNEL_ADMIN & RoleFlags::GLOBAL_PERSONNEL_ADMIN,
-        )
-    {
-        return Err(reject::custom(Fault::Forbidden(format!(
-            "Need to be the user or an admin to delete users"
-        ))));
-    }
-
     // Get email for later use.
     let (user, _etag): (User, _) = cosmos_utils::get(USER_COLLECTION, [&user_id], &user_id).await?;
+    policy::authorize(&claims, policy::USER_DELETE, &Resource::user(&user))?;
     let email = user.email.to_lowercase(); // Normalize.
 
     let code = SecretKey::default();
-    let deleted_user = modify(USER_COLLECTION, [&user_id], &user_id, |mut user: User| {
+    modify(USER_COLLECTION, [&user_id], &user_id, |mut user: User| {
         if user.id != user_id {
             return Err(reject::custom(Fault::IllegalArgument(format!(
                 "user_id does not match url ({} != {}).",
@@ -86,6 +79,20 @@ pub async fn user_delete(
     // Hard delete auth email entry.
     cosmos_utils::delete(AUTH_EMAIL_COLLECTION, [&email], &email, None).await?;
 
+    // Sign out every session of the deleted user.
+    let deleted_user = token::revoke_all(&user_id).await?;
+
+    // The deleted user's details are left out of the audit log.
+    audit::record(
+        &request_id,
+        &claims,
+        policy::USER_DELETE,
+        &user_id,
+        &Resource::user(&user),
+        Changes::default(),
+    )
+    .await;
+
     Ok(warp::reply::json(&DataResponse {
         data: Some(deleted_user),
         extra: None::<Empty>,
@@ -1,10 +1,11 @@
 use crate::fault::Fault;
-use crate::models::{Claims, Device, User};
+use crate::models::{Claims, Device, RefreshTokenFamily, User};
+use crate::policy::{self, Resource};
 use crate::push;
 use crate::util::{log, DataRequest, DataResponse, Empty};
-use crate::{NOTIFICATION_HUB_ACCOUNT, USER_COLLECTION};
+use crate::{NOTIFICATION_HUB_ACCOUNT, REFRESH_TOKEN_COLLECTION, USER_COLLECTION};
 use chrono::Utc;
-use cosmos_utils::{get, upsert};
+use cosmos_utils::{get, modify, upsert};
 use std::sync::Arc;
 use warp::reject;
 
@@ -21,16 +22,30 @@ pub async fn user_device_post(
         return Err(reject::custom(Fault::NoData));
     }
 
-    if user_id != claims.sub {
-        return Err(reject::custom(Fault::Forbidden(format!(
-            "User id does not match signed in user ({} != {}).",
-            user_id, claims.sub
-        ))));
-    }
+    policy::authorize(
+        &claims,
+        policy::USER_CREDENTIALS_PUT,
+        &Resource::owner(&user_id),
+    )?;
 
     let (mut user, etag): (User, String) =
         get(USER_COLLECTION, [&user_id], user_id.clone()).await?;
 
+    // Remember which session the handle belongs to, so that revoking the session unregisters it.
+    if let Some(family_id) = &claims.fam {
+        modify(
+            REFRESH_TOKEN_COLLECTION,
+            [&user_id],
+            family_id,
+            |mut family: RefreshTokenFamily| {
+                family.device = Some(device.clone());
+                family.modified = Utc::now();
+                Ok(family)
+            },
+        )
+        .await?;
+    }
+
     // Duplicate?
     let mut found = false;
     for d in user.devices.iter_mut() {
@@ -0,0 +1,85 @@
+use crate::email;
+use crate::fault::Fault;
+use crate::models::{AuthEmail, Claims, User};
+use crate::password;
+use crate::policy::{self, Resource};
+use crate::throttle::{self, Subject};
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, USER_COLLECTION};
+use cosmos_utils::{get, upsert, CosmosErrorKind};
+use warp::reject;
+
+/// Asks to change the user's email. Nothing changes until the link sent to the new address is
+/// opened, see `confirm_email_change`.
+pub async fn user_email_post(
+    user_id: String,
+    r: DataRequest<String, String>,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let new_email = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+    let password = if let Some(q) = r.extra {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoExtra));
+    };
+    policy::authorize(
+        &claims,
+        policy::USER_CREDENTIALS_PUT,
+        &Resource::owner(&user_id),
+    )?;
+
+    // Normalise email.
+    let new_email = new_email.trim().to_lowercase();
+    if !new_email.contains('@') {
+        return Err(reject::custom(Fault::IllegalArgument(String::from(
+            "Not a valid email",
+        ))));
+    }
+
+    let (mut user, etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+    let email = user.email.to_lowercase();
+    if new_email == email {
+        return Err(reject::custom(Fault::IllegalArgument(String::from(
+            "The email is already the user's email",
+        ))));
+    }
+
+    // The password is asked for so that a stolen session can not take over the account.
+    throttle::check(Subject::Email(&email)).await?;
+    let (auth_email, _): (AuthEmail, _) = get(AUTH_EMAIL_COLLECTION, [&email], &email).await?;
+    if !password::verify(&auth_email.passhash, &password)? {
+        throttle::record_failure(Subject::Email(&email)).await?;
+        return Err(reject::custom(Fault::WrongPassword));
+    }
+
+    let existing: Result<(AuthEmail, _), _> =
+        get(AUTH_EMAIL_COLLECTION, [&new_email], &new_email).await;
+    match existing {
+        Ok(_) => {
+            return Err(reject::custom(Fault::Duplicate(String::from(
+                "The email is already in use",
+            ))))
+        }
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => (),
+            _ => return Err(e.into()),
+        },
+    }
+
+    // Only the link to the latest requested address works.
+    user.pending_email = Some(new_email.clone());
+    user.modified = chrono::Utc::now();
+    upsert(USER_COLLECTION, [&user_id], &user, Some(&etag)).await?;
+
+    email::send_email_change(&user, &new_email).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
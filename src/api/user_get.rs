@@ -1,5 +1,6 @@
 use crate::fault::Fault;
 use crate::models::{Claims, User};
+use crate::policy::{self, Resource};
 use crate::util::{DataResponse, Empty};
 use crate::USER_COLLECTION;
 use cosmos_utils::get;
@@ -7,7 +8,7 @@ use warp::reject;
 
 pub async fn user_get(
     user_id: String,
-    _claims: Claims,
+    claims: Claims,
     _v: u8,
 ) -> Result<impl warp::Reply, warp::Rejection> {
     let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
@@ -19,7 +20,7 @@ pub async fn user_get(
         ))));
     }
 
-    //unimplemented(); //On delete the OFFICE_CONTENT_ADMIN should be any type of office content admin not for a specific office
+    policy::authorize(&claims, policy::USER_GET, &Resource::user(&user))?;
 
     Ok(warp::reply::json(&DataResponse {
         data: Some(user),
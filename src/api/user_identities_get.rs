@@ -0,0 +1,30 @@
+use crate::models::{Claims, LinkedIdentity};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::LINKED_IDENTITY_COLLECTION;
+use cosmos_utils::query_crosspartition;
+
+/// Lists the external identities linked to the signed in user.
+pub async fn user_identities_get(
+    user_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::USER_CREDENTIALS_PUT,
+        &Resource::owner(&user_id),
+    )?;
+
+    let q = format!(
+        "SELECT * FROM {} i WHERE i.userId = '{}'",
+        LINKED_IDENTITY_COLLECTION, user_id
+    );
+    let identities: Vec<LinkedIdentity> =
+        query_crosspartition(LINKED_IDENTITY_COLLECTION, [&()], q, -1, true).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&identities),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,62 @@
+use crate::fault::Fault;
+use crate::models::{AuthEmail, Claims, LinkedIdentity, User};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::{AUTH_EMAIL_COLLECTION, LINKED_IDENTITY_COLLECTION, USER_COLLECTION};
+use cosmos_utils::{delete, get, query_crosspartition};
+use warp::reject;
+
+/// Unlinks an external identity from the signed in user. The last identity of a user without a
+/// password can not be unlinked, as the user could no longer sign in.
+pub async fn user_identity_delete(
+    user_id: String,
+    identity_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    policy::authorize(
+        &claims,
+        policy::USER_CREDENTIALS_PUT,
+        &Resource::owner(&user_id),
+    )?;
+
+    let (identity, etag): (LinkedIdentity, _) =
+        get(LINKED_IDENTITY_COLLECTION, [&identity_id], &identity_id).await?;
+    if identity.user_id != user_id {
+        return Err(reject::custom(Fault::NotFound(String::from(
+            "Could not find identity",
+        ))));
+    }
+
+    let (user, _): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+    let (auth_email, _): (AuthEmail, _) =
+        get(AUTH_EMAIL_COLLECTION, [&user.email], &user.email).await?;
+    if auth_email.passhash.is_empty() {
+        let q = format!(
+            "SELECT * FROM {} i WHERE i.userId = {} AND i.id != {}",
+            LINKED_IDENTITY_COLLECTION,
+            serde_json::json!(user_id),
+            serde_json::json!(identity_id)
+        );
+        let others: Vec<LinkedIdentity> =
+            query_crosspartition(LINKED_IDENTITY_COLLECTION, [&()], q, 1, true).await?;
+        if others.is_empty() {
+            return Err(reject::custom(Fault::IllegalState(String::from(
+                "The last identity can not be unlinked before a password is set",
+            ))));
+        }
+    }
+
+    delete(
+        LINKED_IDENTITY_COLLECTION,
+        [&identity_id],
+        &identity_id,
+        Some(etag),
+    )
+    .await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
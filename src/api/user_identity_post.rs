@@ -0,0 +1,42 @@
+use crate::fault::Fault;
+use crate::identity::{self, Provider};
+use crate::models::Claims;
+use crate::policy::{self, Resource};
+use crate::util::{DataRequest, DataResponse, Empty};
+use warp::reject;
+
+/// Links an identity at an external provider to the signed in user, so that the user can sign in
+/// with it. Takes the same credential as `signin_provider` and an optional nonce as extra.
+pub async fn user_identity_post(
+    user_id: String,
+    provider: String,
+    r: DataRequest<String, String>,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let credential = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+
+    policy::authorize(
+        &claims,
+        policy::USER_CREDENTIALS_PUT,
+        &Resource::owner(&user_id),
+    )?;
+
+    let provider = Provider::find(&provider).ok_or_else(|| {
+        reject::custom(Fault::NotFound(format!(
+            "Unknown identity provider {}",
+            provider
+        )))
+    })?;
+    let external = provider.verify(&credential, r.extra.as_deref()).await?;
+    let linked = identity::link(&external, &user_id).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&linked),
+        extra: None::<Empty>,
+    }))
+}
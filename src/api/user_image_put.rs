@@ -1,11 +1,10 @@
-use crate::fault::Fault;
 use crate::models::{Claims, User};
+use crate::policy::{self, Resource};
 use crate::util::{DataResponse, Empty};
 use crate::USER_COLLECTION;
 use chrono::Utc;
 use cosmos_utils::{get, upload_image, upsert};
 use warp::filters::multipart::FormData;
-use warp::reject;
 
 pub async fn user_image_put(
     id: String,
@@ -13,14 +12,9 @@ pub async fn user_image_put(
     _v: u8,
     f: FormData,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    let (mut user, etag): (User, _) = get(USER_COLLECTION, [&id], &id).await?;
+    policy::authorize(&claims, policy::USER_PUT, &Resource::owner(&id))?;
 
-    if claims.sub != id {
-        return Err(reject::custom(Fault::Forbidden(format!(
-            "Caller is not the put user ({} != {})",
-            claims.sub, id
-        ))));
-    }
+    let (mut user, etag): (User, _) = get(USER_COLLECTION, [&id], &id).await?;
 
     let image_id = upload_image(f).await?;
     user.images.push(image_id);
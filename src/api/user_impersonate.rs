@@ -0,0 +1,69 @@
+use crate::audit::{self, Changes};
+use crate::fault::Fault;
+use crate::models::{Claims, User};
+use crate::policy::{self, Resource};
+use crate::token;
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::USER_COLLECTION;
+use cosmos_utils::get;
+use serde_json::json;
+use warp::reject;
+
+/// Issues a short lived token for the calling admin to act as the user, e.g. to see what the
+/// user's poll returns. The token is read-only unless `data` is true, carries none of the user's
+/// office admin roles and is never allowed to change passwords, roles or payments. Every request
+/// made with it is recorded in the audit log against the admin. `extra` of the response is the
+/// token's expiry.
+pub async fn user_impersonate(
+    user_id: String,
+    r: DataRequest<bool, Empty>,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let write = r.data.unwrap_or(false);
+
+    let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+    let resource = Resource::user(&user);
+    policy::authorize(&claims, policy::USER_IMPERSONATE, &resource)?;
+    if user.deleted {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "User {} is deleted",
+            user_id
+        ))));
+    }
+    if user.id == claims.sub {
+        return Err(reject::custom(Fault::IllegalArgument(String::from(
+            "Cannot impersonate yourself.",
+        ))));
+    }
+    // Global roles would let the admin act with more than their own roles.
+    if user.roles.iter().any(|role| role.sub.is_none()) {
+        return Err(reject::custom(Fault::Forbidden(String::from(
+            "Users with global roles can not be impersonated.",
+        ))));
+    }
+
+    let (access_token, expires) = token::encode_impersonation_token(&user, &claims.sub, write)?;
+
+    // The user is not changed, the entry records the token issued.
+    let mut changes = Changes::default();
+    changes.after.insert(String::from("write"), json!(write));
+    changes
+        .after
+        .insert(String::from("expires"), json!(expires));
+    audit::record(
+        &request_id,
+        &claims,
+        policy::USER_IMPERSONATE,
+        &user.id,
+        &resource,
+        changes,
+    )
+    .await;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(access_token),
+        extra: Some(expires),
+    }))
+}
@@ -0,0 +1,40 @@
+use crate::audit;
+use crate::membership;
+use crate::models::{Claims, User};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::USER_COLLECTION;
+use cosmos_utils::{get, upsert};
+
+/// Removes the user from the office, along with the user's roles for it. Users can leave offices
+/// by themselves.
+pub async fn user_office_delete(
+    user_id: String,
+    office_id: String,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let resource = Resource::owner(&user_id).in_office(&office_id);
+    policy::authorize(&claims, policy::USER_OFFICE_DELETE, &resource)?;
+
+    let (mut user, etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+    let old_user = user.clone();
+    if membership::remove(&mut user, &office_id) {
+        upsert(USER_COLLECTION, [&user_id], &user, Some(&etag)).await?;
+        audit::record(
+            &request_id,
+            &claims,
+            policy::USER_OFFICE_DELETE,
+            &user_id,
+            &resource,
+            audit::changes(Some(&old_user), Some(&user)),
+        )
+        .await;
+    }
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(user),
+        extra: None::<Empty>,
+    }))
+}
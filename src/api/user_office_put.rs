@@ -0,0 +1,69 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::membership;
+use crate::models::{Claims, Office, User};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::{OFFICE_COLLECTION, USER_COLLECTION};
+use cosmos_utils::{get, upsert};
+use warp::reject;
+
+/// Adds the user to the office. Users holding a role outside the office, including every global
+/// role, can only join through an invitation or a join code.
+pub async fn user_office_put(
+    user_id: String,
+    office_id: String,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let resource = Resource::owner(&user_id).in_office(&office_id);
+    policy::authorize(&claims, policy::USER_OFFICE_PUT, &resource)?;
+
+    let (office, _): (Office, _) = get(OFFICE_COLLECTION, [&office_id], &office_id).await?;
+    if office.deleted {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "Office {} is deleted",
+            office_id
+        ))));
+    }
+    let (mut user, etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+    if user.deleted {
+        return Err(reject::custom(Fault::NotFound(format!(
+            "User {} is deleted",
+            user_id
+        ))));
+    }
+
+    // The office's admins gain rights over the users of the office, so an admin must not be able to
+    // pull in someone holding rights elsewhere without their consent.
+    if user
+        .roles
+        .iter()
+        .any(|role| role.sub.as_deref() != Some(office_id.as_str()))
+    {
+        return Err(reject::custom(Fault::Forbidden(format!(
+            "User {} holds roles outside office {}",
+            user_id, office_id
+        ))));
+    }
+
+    let old_user = user.clone();
+    if membership::add(&mut user, &office_id) {
+        upsert(USER_COLLECTION, [&user_id], &user, Some(&etag)).await?;
+        audit::record(
+            &request_id,
+            &claims,
+            policy::USER_OFFICE_PUT,
+            &user_id,
+            &resource,
+            audit::changes(Some(&old_user), Some(&user)),
+        )
+        .await;
+    }
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(user),
+        extra: None::<Empty>,
+    }))
+}
@@ -1,13 +1,15 @@
 use crate::fault::Fault;
 use crate::models::{
-    Category, Claims, Episode, EpisodeMetadata, Office, Recommendation, Series, SeriesUserData,
-    Subscription, User,
+    Category, Claims, Episode, EpisodeMetadata, Office, OfficeSettings, Recommendation, Series,
+    SeriesUserData, Subscription, User,
 };
+use crate::policy::{self, Resource};
+use crate::product_catalog;
 use crate::util::{self, DataResponse, Empty};
 use crate::{
     CATEGORY_COLLECTION, EPISODE_COLLECTION, EPISODE_METADATA_COLLECTION, OFFICE_COLLECTION,
-    RECOMMENDED_COLLECTION, SERIES_COLLECTION, SERIES_USER_DATA_COLLECTION,
-    SUBSCRIPTION_COLLECTION, USER_COLLECTION,
+    OFFICE_SETTINGS_COLLECTION, RECOMMENDED_COLLECTION, SERIES_COLLECTION,
+    SERIES_USER_DATA_COLLECTION, SUBSCRIPTION_COLLECTION, USER_COLLECTION,
 };
 use chrono::{DateTime, Utc};
 use cosmos_utils::{get, query, CosmosErrorStruct};
@@ -27,6 +29,9 @@ pub struct UserPollDataResponse<'a> {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub offices: Vec<Office>,
 
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub office_settings: Vec<OfficeSettings>,
+
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub recommendations: Vec<Recommendation>,
 
@@ -56,19 +61,24 @@ pub async fn user_poll(
     _range: u16,
     since: Option<DateTime<Utc>>,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    if user_id != claims.sub {
-        return Err(reject::custom(Fault::Forbidden(format!(
-            "User id does not match signed in user ({} != {}).",
-            user_id, claims.sub
-        ))));
-    }
+    policy::authorize(&claims, policy::USER_POLL, &Resource::owner(&user_id))?;
 
     let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], user_id.clone()).await?;
+    // Offices joined since the last poll are sent in full. Of offices deleted since, only the
+    // deletion is sent, so that devices drop what they have cached of the office.
     let office_ids: Vec<_> = user
         .office_ids
         .iter()
-        .cloned()
-        .map(|o| Arc::new(o))
+        .map(|o| {
+            let joined_at = user.office_joined_at.get(o);
+            let office_since = since.filter(|s| joined_at.is_none_or(|j| j < s));
+            (Arc::new(o.clone()), office_since, false)
+        })
+        .chain(since.iter().flat_map(|s| {
+            user.deleted_office_ids
+                .iter()
+                .map(move |o| (Arc::new(o.clone()), Some(*s), true))
+        }))
         .collect();
     let user_id = Arc::new(user_id);
 
@@ -84,9 +94,10 @@ pub async fn user_poll(
     let user = new_user;
 
     let mut offices = vec![];
-    for office_id in &office_ids {
+    for (office_id, since, _) in &office_ids {
         // Offices
         let office_id = office_id.clone();
+        let since = *since;
         offices.push(async move {
             let (off, _): (Office, _) = get(
                 OFFICE_COLLECTION,
@@ -108,15 +119,39 @@ pub async fn user_poll(
     }
     let offices = futures::future::join_all(offices);
 
-    let since = match since {
-        Some(since) => format!(r#" WHERE o.modified >= "{}""#, since.to_rfc3339()),
-        None => String::from(""),
-    };
+    let mut office_settings = vec![];
+    for (office_id, office_since, office_deleted) in &office_ids {
+        // Office settings
+        if *office_deleted {
+            continue;
+        }
+        let q = format!(
+            "SELECT * FROM {} o{}",
+            OFFICE_SETTINGS_COLLECTION,
+            modified_since(*office_since)
+        );
+        let office_id = office_id.clone();
+        office_settings.push(async move {
+            let mut set: Vec<OfficeSettings> =
+                query(OFFICE_SETTINGS_COLLECTION, [&office_id.as_ref()], q, -1).await?;
+            for settings in &mut set {
+                settings.product_ids = product_catalog::product_ids(&office_id).await?;
+            }
+            Result::<_, CosmosErrorStruct>::Ok(set)
+        });
+    }
+    let office_settings = futures::future::join_all(office_settings);
+
+    let since = modified_since(since);
 
     let mut recommendations = vec![];
-    for office_id in &office_ids {
+    for (office_id, office_since, office_deleted) in &office_ids {
         // Recommendations
-        let q = format!("SELECT * FROM {} o{}", RECOMMENDED_COLLECTION, since);
+        let q = format!(
+            "SELECT * FROM {} o{}",
+            RECOMMENDED_COLLECTION,
+            office_content_since(*office_since, *office_deleted)
+        );
         let office_id = office_id.clone();
         recommendations.push(async move {
             let rec: Vec<Recommendation> =
@@ -127,9 +162,13 @@ pub async fn user_poll(
     let recommendations = futures::future::join_all(recommendations);
 
     let mut categories = vec![];
-    for office_id in &office_ids {
+    for (office_id, office_since, office_deleted) in &office_ids {
         // Categories
-        let q = format!("SELECT * FROM {} o{}", &*CATEGORY_COLLECTION, since);
+        let q = format!(
+            "SELECT * FROM {} o{}",
+            &*CATEGORY_COLLECTION,
+            office_content_since(*office_since, *office_deleted)
+        );
         let office_id = office_id.clone();
         categories.push(async move {
             let cat: Vec<Category> =
@@ -140,9 +179,13 @@ pub async fn user_poll(
     let categories = futures::future::join_all(categories);
 
     let mut series = vec![];
-    for office_id in &office_ids {
+    for (office_id, office_since, office_deleted) in &office_ids {
         // Series
-        let q = format!("SELECT * FROM {} o{}", &*SERIES_COLLECTION, since);
+        let q = format!(
+            "SELECT * FROM {} o{}",
+            &*SERIES_COLLECTION,
+            office_content_since(*office_since, *office_deleted)
+        );
         let office_id = office_id.clone();
         series.push(async move {
             let ser: Vec<Series> = query(SERIES_COLLECTION, [&office_id.as_ref()], q, -1).await?;
@@ -152,9 +195,13 @@ pub async fn user_poll(
     let series = futures::future::join_all(series);
 
     let mut episodes = vec![];
-    for office_id in &office_ids {
+    for (office_id, office_since, office_deleted) in &office_ids {
         // Episodes
-        let q = format!("SELECT * FROM {} o{}", &*EPISODE_COLLECTION, since);
+        let q = format!(
+            "SELECT * FROM {} o{}",
+            &*EPISODE_COLLECTION,
+            office_content_since(*office_since, *office_deleted)
+        );
         let office_id = office_id.clone();
         episodes.push(async move {
             let epi: Vec<Episode> = query(EPISODE_COLLECTION, [&office_id.as_ref()], q, -1).await?;
@@ -191,6 +238,7 @@ pub async fn user_poll(
 
     let (
         offices_r,
+        office_settings_r,
         recommendations_r,
         categories_r,
         series_r,
@@ -200,6 +248,7 @@ pub async fn user_poll(
         subscriptions_r,
     ) = tokio::join!(
         offices,
+        office_settings,
         recommendations,
         categories,
         series,
@@ -216,6 +265,10 @@ pub async fn user_poll(
             offices.push(office);
         }
     }
+    let mut office_settings: Vec<OfficeSettings> = vec![];
+    for settings in office_settings_r {
+        office_settings.extend(settings?);
+    }
     let mut recommendations: Vec<Recommendation> = vec![];
     for recommendation in recommendations_r {
         recommendations.extend(recommendation?);
@@ -261,6 +314,7 @@ pub async fn user_poll(
         data: Some(&UserPollDataResponse {
             user,
             offices,
+            office_settings,
             recommendations,
             categories,
             series,
@@ -288,3 +342,20 @@ pub async fn user_poll(
         )
         .body(res))
 }
+
+/// Condition for the content of an office to send, of a deleted office only the documents marked by
+/// its deletion.
+fn office_content_since(since: Option<DateTime<Utc>>, office_deleted: bool) -> String {
+    match (since, office_deleted) {
+        (_, false) => modified_since(since),
+        (None, true) => String::from(" WHERE o.officeDeleted = true"),
+        (Some(_), true) => format!("{} AND o.officeDeleted = true", modified_since(since)),
+    }
+}
+
+fn modified_since(since: Option<DateTime<Utc>>) -> String {
+    match since {
+        Some(since) => format!(r#" WHERE o.modified >= "{}""#, since.to_rfc3339()),
+        None => String::from(""),
+    }
+}
@@ -1,5 +1,6 @@
 use crate::fault::Fault;
 use crate::models::{Claims, User};
+use crate::policy::{self, Resource};
 use crate::util::{DataRequest, DataResponse, Empty};
 use crate::USER_COLLECTION;
 use cosmos_utils::modify;
@@ -25,21 +26,26 @@ pub async fn user_put(
         ))));
     }
 
-    if user_id != claims.sub {
-        return Err(reject::custom(Fault::Forbidden(format!(
-            "Caller is not the PUT user"
-        ))));
-    }
+    policy::authorize(&claims, policy::USER_PUT, &Resource::owner(&user_id))?;
 
     let user = modify(USER_COLLECTION, [&user_id], &user_id, |user: User| {
         let mut new_user = new_user.clone();
         new_user.deleted = user.deleted;
         new_user.test = user.test;
+        new_user.guest = user.guest;
+        new_user.guest_device = user.guest_device;
         new_user.roles = user.roles;
         new_user.devices = user.devices;
         new_user.images = user.images;
         new_user.email = user.email;
+        new_user.email_verified_at = user.email_verified_at;
+        new_user.email_verification_sent_at = user.email_verification_sent_at;
+        new_user.pending_email = user.pending_email;
         new_user.office_ids = user.office_ids;
+        new_user.office_joined_at = user.office_joined_at;
+        new_user.deleted_office_ids = user.deleted_office_ids;
+        new_user.token_generation = user.token_generation;
+        new_user.role_generation = user.role_generation;
         new_user.modified = chrono::Utc::now();
         Ok(new_user)
     })
@@ -0,0 +1,33 @@
+use crate::fault::Fault;
+use crate::models::{Claims, Role};
+use crate::roles;
+use crate::util::{DataRequest, DataResponse, Empty};
+use warp::reject;
+
+/// Grants roles to the user, roles the user already has are left as they are.
+/// NOTE: This endpoint can not be used to assign a craftsman role to the user, for that you should
+/// create a craftsman
+pub async fn user_roles_post(
+    user_id: String,
+    r: DataRequest<Vec<Role>, Empty>,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let granted;
+    if let Some(q) = r.data {
+        granted = q;
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    }
+
+    let user = roles::apply(&user_id, &claims, &request_id, |current| {
+        current.iter().cloned().chain(granted).collect()
+    })
+    .await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(user),
+        extra: None::<Empty>,
+    }))
+}
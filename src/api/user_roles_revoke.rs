@@ -0,0 +1,32 @@
+use crate::fault::Fault;
+use crate::models::{Claims, Role};
+use crate::roles;
+use crate::util::{DataRequest, DataResponse, Empty};
+use warp::reject;
+
+/// Revokes roles from the user, e.g. `{ flg: OFFICE_CONTENT_ADMIN, sub: office }` removes only that
+/// flag for that office. Roles the user does not have are ignored.
+pub async fn user_roles_revoke(
+    user_id: String,
+    r: DataRequest<Vec<Role>, Empty>,
+    claims: Claims,
+    request_id: String,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let revoked;
+    if let Some(q) = r.data {
+        revoked = q;
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    }
+
+    let user = roles::apply(&user_id, &claims, &request_id, |current| {
+        roles::difference(current, &revoked)
+    })
+    .await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(user),
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,29 @@
+use crate::models::{Claims, User};
+use crate::policy::{self, Resource};
+use crate::token;
+use crate::util::{DataResponse, Empty};
+use crate::USER_COLLECTION;
+use cosmos_utils::get;
+
+/// Signs out one of the user's sessions, e.g. a lost device. Available to the user and to global
+/// personnel admins.
+pub async fn user_session_delete(
+    user_id: String,
+    session_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+    policy::authorize(
+        &claims,
+        policy::USER_SESSIONS_DELETE,
+        &Resource::user(&user),
+    )?;
+
+    token::revoke(&user_id, &session_id).await?;
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
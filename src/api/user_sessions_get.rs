@@ -0,0 +1,62 @@
+use crate::models::{Claims, Device, RefreshTokenFamily, User};
+use crate::policy::{self, Resource};
+use crate::util::{DataResponse, Empty};
+use crate::{REFRESH_TOKEN_COLLECTION, USER_COLLECTION};
+use chrono::{DateTime, Utc};
+use cosmos_utils::{get, query_crosspartition};
+use serde::Serialize;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session<'a> {
+    pub id: &'a str,
+    pub device: Option<&'a Device>,
+    pub ip: Option<&'a str>,
+    pub two_factor: bool,
+    // Whether this is the session of the calling access token.
+    pub current: bool,
+    pub created: DateTime<Utc>,
+    pub refreshed: Option<DateTime<Utc>>,
+    pub expires: DateTime<Utc>,
+}
+
+/// Lists the sessions the user is signed in with, most recently used first. Available to the user
+/// and to personnel admins of the user.
+pub async fn user_sessions_get(
+    user_id: String,
+    claims: Claims,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (user, _etag): (User, _) = get(USER_COLLECTION, [&user_id], &user_id).await?;
+    policy::authorize(&claims, policy::USER_SESSIONS_GET, &Resource::user(&user))?;
+
+    let q = format!(
+        "SELECT * FROM {} f WHERE NOT IS_DEFINED(f.revoked)",
+        REFRESH_TOKEN_COLLECTION
+    );
+    let mut families: Vec<RefreshTokenFamily> =
+        query_crosspartition(REFRESH_TOKEN_COLLECTION, [&user_id], q, -1, false).await?;
+    // Expired sessions are never marked as revoked.
+    let now = Utc::now();
+    families.retain(|f| f.expires > now);
+    families.sort_by_key(|f| std::cmp::Reverse(f.refreshed.unwrap_or(f.created)));
+
+    let sessions: Vec<Session> = families
+        .iter()
+        .map(|f| Session {
+            id: &f.id,
+            device: f.device.as_ref(),
+            ip: f.ip.as_deref(),
+            two_factor: f.two_factor,
+            current: claims.fam.as_ref() == Some(&f.id),
+            created: f.created,
+            refreshed: f.refreshed,
+            expires: f.expires,
+        })
+        .collect();
+
+    Ok(warp::reply::json(&DataResponse {
+        data: Some(&sessions),
+        extra: None::<Empty>,
+    }))
+}
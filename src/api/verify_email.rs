@@ -0,0 +1,43 @@
+use crate::fault::Fault;
+use crate::models::{TokenPurpose, User};
+use crate::token;
+use crate::util::{DataRequest, DataResponse, Empty};
+use crate::USER_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::{get, upsert};
+use warp::reject;
+
+/// Marks the user's email as verified given the token from a verification email.
+pub async fn verify_email(
+    r: DataRequest<String, Empty>,
+    _v: u8,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let verification_token = if let Some(q) = r.data {
+        q
+    } else {
+        return Err(reject::custom(Fault::NoData));
+    };
+
+    let claims = token::decode_purpose_token(&verification_token, TokenPurpose::VerifyEmail)?;
+
+    let (mut user, etag): (User, _) = get(USER_COLLECTION, [&claims.sub], &claims.sub).await?;
+
+    // The link is only good for the address it was sent to.
+    if claims.eml.as_ref() != Some(&user.email) {
+        return Err(reject::custom(Fault::IllegalState(String::from(
+            "The email has changed since the verification email was sent",
+        ))));
+    }
+
+    // Verifying twice is fine, e.g. if the link is opened again.
+    if user.email_verified_at.is_none() {
+        user.email_verified_at = Some(Utc::now());
+        user.modified = Utc::now();
+        upsert(USER_COLLECTION, [&user.id], &user, Some(&etag)).await?;
+    }
+
+    Ok(warp::reply::json(&DataResponse {
+        data: None::<Empty>,
+        extra: None::<Empty>,
+    }))
+}
@@ -0,0 +1,39 @@
+use super::IGNORED_FIELDS;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// The top level fields that differ between two versions of a document, as they were before and
+/// after.
+#[derive(Debug, Default)]
+pub struct Changes {
+    pub before: Map<String, Value>,
+    pub after: Map<String, Value>,
+}
+
+/// The fields that differ between `before` and `after`. A missing version, e.g. before a post,
+/// counts as a document without fields.
+pub fn changes<T: Serialize>(before: Option<&T>, after: Option<&T>) -> Changes {
+    let before = fields(before);
+    let after = fields(after);
+
+    let mut changes = Changes::default();
+    for key in before.keys().chain(after.keys()) {
+        if IGNORED_FIELDS.contains(&key.as_str()) || before.get(key) == after.get(key) {
+            continue;
+        }
+        if let Some(value) = before.get(key) {
+            changes.before.insert(key.clone(), value.clone());
+        }
+        if let Some(value) = after.get(key) {
+            changes.after.insert(key.clone(), value.clone());
+        }
+    }
+    changes
+}
+
+fn fields<T: Serialize>(document: Option<&T>) -> Map<String, Value> {
+    match document.map(serde_json::to_value) {
+        Some(Ok(Value::Object(fields))) => fields,
+        _ => Map::new(),
+    }
+}
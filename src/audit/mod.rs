@@ -0,0 +1,20 @@
+//! The append-only audit log of privileged and administrative actions. Handlers record an action
+//! once it has been performed, with the top level fields of the target it changed.
+
+/// Fields left out of the recorded changes, they change with every write.
+const IGNORED_FIELDS: &[&str] = &["modified"];
+
+/// Action of the entries recording each request made with an impersonation token.
+const IMPERSONATED_REQUEST: &str = "impersonated_request";
+
+mod changes;
+pub use changes::{changes, Changes};
+
+mod record;
+pub use record::record;
+
+mod record_request;
+pub use record_request::record_request;
+
+mod query;
+pub use query::query;
@@ -0,0 +1,39 @@
+use crate::models::{AuditEntry, AuditQuery};
+use crate::AUDIT_COLLECTION;
+use cosmos_utils::query_crosspartition;
+
+/// The entries matching the filter, most recent first.
+pub async fn query(filter: &AuditQuery, limit: u16) -> Result<Vec<AuditEntry>, warp::Rejection> {
+    // Values are written as json strings, which quotes and escapes them for the query.
+    let mut conditions = vec![];
+    if let Some(office_id) = &filter.office_id {
+        conditions.push(format!(
+            "ARRAY_CONTAINS(a.officeIds, {})",
+            serde_json::json!(office_id)
+        ));
+    }
+    if let Some(actor) = &filter.actor {
+        conditions.push(format!("a.actor = {}", serde_json::json!(actor)));
+    }
+    if let Some(from) = &filter.from {
+        conditions.push(format!("a.created >= {}", serde_json::json!(from)));
+    }
+    if let Some(to) = &filter.to {
+        conditions.push(format!("a.created < {}", serde_json::json!(to)));
+    }
+
+    let q = if conditions.is_empty() {
+        format!(
+            "SELECT * FROM {} a ORDER BY a.created DESC",
+            AUDIT_COLLECTION
+        )
+    } else {
+        format!(
+            "SELECT * FROM {} a WHERE {} ORDER BY a.created DESC",
+            AUDIT_COLLECTION,
+            conditions.join(" AND ")
+        )
+    };
+    let entries = query_crosspartition(AUDIT_COLLECTION, [&()], q, i32::from(limit), true).await?;
+    Ok(entries)
+}
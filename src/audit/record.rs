@@ -0,0 +1,38 @@
+use super::Changes;
+use crate::models::{AuditEntry, Claims};
+use crate::policy::{Action, Resource};
+use crate::util::{self, log_critical};
+use crate::AUDIT_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::insert;
+
+/// Records that the claims performed the action on the target. The action has already been
+/// performed, so a failure to record it is logged rather than failing the request. Actions
+/// performed with an impersonation token are recorded against the impersonating admin.
+pub async fn record(
+    request_id: &str,
+    claims: &Claims,
+    action: Action,
+    target: &str,
+    resource: &Resource<'_>,
+    changes: Changes,
+) {
+    let entry = AuditEntry {
+        id: util::new_guid_v4(),
+        actor: claims.act.clone().unwrap_or_else(|| claims.sub.clone()),
+        impersonated: claims.act.as_ref().map(|_| claims.sub.clone()),
+        action: String::from(action.name),
+        target: target.to_string(),
+        office_ids: resource.offices.iter().map(|o| o.to_string()).collect(),
+        before: changes.before,
+        after: changes.after,
+        request_id: request_id.to_string(),
+        created: Utc::now(),
+    };
+    if let Err(e) = insert(AUDIT_COLLECTION, [&entry.id], &entry, None).await {
+        log_critical(format!(
+            "Could not record {} of {} by {} in the audit log due to {:?}",
+            action.name, target, claims.sub, e
+        ));
+    }
+}
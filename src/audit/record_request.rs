@@ -0,0 +1,34 @@
+use super::IMPERSONATED_REQUEST;
+use crate::models::{AuditEntry, Claims};
+use crate::util::{self, log_critical};
+use crate::AUDIT_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::insert;
+use warp::http::Method;
+
+/// Records a request made with an impersonation token against the impersonating admin, before it
+/// is handled. Does nothing for other tokens.
+pub async fn record_request(request_id: &str, claims: &Claims, method: &Method, path: &str) {
+    let actor = match &claims.act {
+        Some(actor) => actor,
+        None => return,
+    };
+    let entry = AuditEntry {
+        id: util::new_guid_v4(),
+        actor: actor.clone(),
+        impersonated: Some(claims.sub.clone()),
+        action: String::from(IMPERSONATED_REQUEST),
+        target: format!("{} {}", method, path),
+        office_ids: vec![],
+        before: Default::default(),
+        after: Default::default(),
+        request_id: request_id.to_string(),
+        created: Utc::now(),
+    };
+    if let Err(e) = insert(AUDIT_COLLECTION, [&entry.id], &entry, None).await {
+        log_critical(format!(
+            "Could not record request {} by {} as {} in the audit log due to {:?}",
+            entry.target, actor, claims.sub, e
+        ));
+    }
+}
@@ -0,0 +1,20 @@
+/// Address transactional emails are sent from.
+const SENDER: &str = "info@primecrime.se";
+
+mod send;
+pub use send::send;
+
+mod send_verification;
+pub use send_verification::send_verification;
+
+mod send_email_change;
+pub use send_email_change::send_email_change;
+
+mod send_signin_link;
+pub use send_signin_link::send_signin_link;
+
+mod send_password_reset;
+pub use send_password_reset::send_password_reset;
+
+mod send_invitation;
+pub use send_invitation::send_invitation;
@@ -0,0 +1,20 @@
+use super::SENDER;
+use crate::fault::Fault;
+use crate::SENDGRID_API_KEY;
+use sendgrid::v3::*;
+use warp::reject;
+
+/// Sends the SendGrid dynamic template to a single recipient.
+pub async fn send(to: &str, template_id: &str, data: SGMap) -> Result<(), warp::Rejection> {
+    let p = Personalization::new(Email::new(to)).add_dynamic_template_data(data);
+
+    let m = Message::new(Email::new(SENDER))
+        .set_template_id(template_id)
+        .add_personalization(p);
+    let sender = Sender::new(SENDGRID_API_KEY.to_string());
+    sender
+        .send(&m)
+        .await
+        .map_err(|e| reject::custom(Fault::Unspecified(format!("Could not send email: {}", e))))?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use crate::models::{TokenPurpose, User};
+use crate::token;
+use crate::{EMAIL_CHANGE_TEMPLATE_ID, EMAIL_CHANGE_URL};
+use chrono::{Duration, Utc};
+use sendgrid::v3::SGMap;
+
+/// How long the link confirming a change of email works.
+const EMAIL_CHANGE_LIFETIME_HOURS: i64 = 24;
+
+/// Sends a link to the new address for confirming that the user owns it.
+pub async fn send_email_change(user: &User, new_email: &str) -> Result<(), warp::Rejection> {
+    let exp = Utc::now() + Duration::hours(EMAIL_CHANGE_LIFETIME_HOURS);
+    let token = token::encode_purpose_token(&user.id, TokenPurpose::ChangeEmail, new_email, exp)?;
+
+    let mut map = SGMap::new();
+    map.insert(String::from("firstName"), user.first_name.clone());
+    map.insert(
+        String::from("confirmationUrl"),
+        format!("{}?token={}", *EMAIL_CHANGE_URL, token),
+    );
+    super::send(new_email, &EMAIL_CHANGE_TEMPLATE_ID, map).await
+}
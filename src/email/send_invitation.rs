@@ -0,0 +1,14 @@
+use crate::{INVITATION_TEMPLATE_ID, INVITATION_URL};
+use sendgrid::v3::SGMap;
+
+/// Sends a link for accepting an invitation to an office. The link carries the accept token,
+/// which is also included on its own for apps that let the user paste it in.
+pub async fn send_invitation(email: &str, accept_token: &str) -> Result<(), warp::Rejection> {
+    let mut map = SGMap::new();
+    map.insert(
+        String::from("acceptUrl"),
+        format!("{}?token={}", *INVITATION_URL, accept_token),
+    );
+    map.insert(String::from("acceptToken"), accept_token.to_string());
+    super::send(email, &INVITATION_TEMPLATE_ID, map).await
+}
@@ -0,0 +1,14 @@
+use crate::{PASSWORD_RESET_TEMPLATE_ID, PASSWORD_RESET_URL};
+use sendgrid::v3::SGMap;
+
+/// Sends a link for choosing a new password. The link carries the reset token, which is also
+/// included on its own for apps that let the user type it in.
+pub async fn send_password_reset(email: &str, reset_token: &str) -> Result<(), warp::Rejection> {
+    let mut map = SGMap::new();
+    map.insert(
+        String::from("resetUrl"),
+        format!("{}?token={}", *PASSWORD_RESET_URL, reset_token),
+    );
+    map.insert(String::from("resetToken"), reset_token.to_string());
+    super::send(email, &PASSWORD_RESET_TEMPLATE_ID, map).await
+}
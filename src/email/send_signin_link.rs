@@ -0,0 +1,23 @@
+use crate::{SIGNIN_LINK_TEMPLATE_ID, SIGNIN_LINK_URL};
+use sendgrid::v3::SGMap;
+use url::form_urlencoded::byte_serialize;
+
+/// Sends a link for signing in without a password, along with a code for apps that let the user
+/// type it in instead.
+pub async fn send_signin_link(
+    email: &str,
+    link_token: &str,
+    code: &str,
+) -> Result<(), warp::Rejection> {
+    let encoded_email: String = byte_serialize(email.as_bytes()).collect();
+    let mut map = SGMap::new();
+    map.insert(
+        String::from("signinUrl"),
+        format!(
+            "{}?email={}&token={}",
+            *SIGNIN_LINK_URL, encoded_email, link_token
+        ),
+    );
+    map.insert(String::from("code"), code.to_string());
+    super::send(email, &SIGNIN_LINK_TEMPLATE_ID, map).await
+}
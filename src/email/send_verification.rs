@@ -0,0 +1,22 @@
+use crate::models::{TokenPurpose, User};
+use crate::token;
+use crate::{EMAIL_VERIFICATION_TEMPLATE_ID, EMAIL_VERIFICATION_URL};
+use chrono::{Duration, Utc};
+use sendgrid::v3::SGMap;
+
+/// How long the link in a verification email works.
+const VERIFICATION_LIFETIME_HOURS: i64 = 48;
+
+/// Sends the user a link for verifying the user's current email address.
+pub async fn send_verification(user: &User) -> Result<(), warp::Rejection> {
+    let exp = Utc::now() + Duration::hours(VERIFICATION_LIFETIME_HOURS);
+    let token = token::encode_purpose_token(&user.id, TokenPurpose::VerifyEmail, &user.email, exp)?;
+
+    let mut map = SGMap::new();
+    map.insert(String::from("firstName"), user.first_name.clone());
+    map.insert(
+        String::from("verificationUrl"),
+        format!("{}?token={}", *EMAIL_VERIFICATION_URL, token),
+    );
+    super::send(&user.email, &EMAIL_VERIFICATION_TEMPLATE_ID, map).await
+}
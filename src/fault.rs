@@ -2,7 +2,7 @@
 
 use warp::reject;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Fault {
     Unspecified(String),
     Set(Vec<Fault>),
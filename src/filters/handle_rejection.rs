@@ -3,7 +3,7 @@ use crate::util::log;
 use cosmos_utils::{CosmosErrorKind, CosmosErrorStruct};
 use serde::Serialize;
 use std::convert::Infallible;
-use warp::{http::StatusCode, Rejection, Reply};
+use warp::{http::StatusCode, reject, Rejection, Reply};
 
 #[derive(Serialize)]
 struct FaultResponse {
@@ -14,16 +14,34 @@ struct FaultResponse {
 struct FaultResponseBody {
     code: i32,
     text: String,
+    // The individual faults of a `Fault::Set`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    faults: Vec<FaultResponseBody>,
 }
 
 // This function receives a `Rejection` and tries to return a custom
 // value, otherwise simply passes the rejection along.
 pub async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible> {
     let (status, code, text) = parse_error(&err);
+    let faults = match err.find::<Fault>() {
+        Some(Fault::Set(faults)) => faults
+            .iter()
+            .map(|fault| {
+                let (_, code, text) = parse_error(&reject::custom(fault.clone()));
+                FaultResponseBody {
+                    code,
+                    text,
+                    faults: vec![],
+                }
+            })
+            .collect(),
+        _ => vec![],
+    };
     let json = warp::reply::json(&FaultResponse {
         fault: FaultResponseBody {
             code,
             text: text.into(),
+            faults,
         },
     });
     Ok(warp::reply::with_status(json, status))
@@ -46,9 +64,13 @@ pub fn parse_error(err: &Rejection) -> (StatusCode, i32, String) {
                 status = StatusCode::INTERNAL_SERVER_ERROR; //StatusCode::from_u16(500).unwrap();
                 text = g;
             }
-            Fault::Set(_) => {
+            Fault::Set(faults) => {
                 code = FaultCode::Set as i32;
-                status = StatusCode::INTERNAL_SERVER_ERROR;
+                // The set is as severe as its first fault.
+                status = match faults.first() {
+                    Some(fault) => parse_error(&reject::custom(fault.clone())).0,
+                    None => StatusCode::INTERNAL_SERVER_ERROR,
+                };
                 text = "Set.";
             }
             Fault::ApiLevelNoLongerSupported => {
@@ -2,11 +2,20 @@ mod with_version;
 pub use with_version::with_version;
 
 mod with_token;
-pub use with_token::with_token;
+pub use with_token::{with_token, with_token_and_request_id};
+
+mod with_principal;
+pub use with_principal::with_principal;
 
 mod with_optional_token;
 pub use with_optional_token::with_optional_token;
 
+mod with_client_ip;
+pub use with_client_ip::with_client_ip;
+
+mod with_request_id;
+pub use with_request_id::with_request_id;
+
 mod with_range;
 pub use with_range::with_range;
 
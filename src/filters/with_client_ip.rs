@@ -0,0 +1,46 @@
+use crate::TRUSTED_PROXY_HOPS;
+use std::net::{IpAddr, SocketAddr};
+use warp::{Filter, Rejection};
+
+/// Extracts the ip of the client. Entries to the left in `X-Forwarded-For` are set by the client
+/// and can not be trusted, so the ip is the entry added by the outermost trusted proxy, counted
+/// `TRUSTED_PROXY_HOPS` from the right. Without such an entry the ip is the socket's.
+pub fn with_client_ip() -> impl Filter<Extract = (String,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("X-Forwarded-For")
+        .and(warp::addr::remote())
+        .map(|forwarded: Option<String>, remote: Option<SocketAddr>| {
+            let forwarded = forwarded.and_then(|f| forwarded_ip(&f, *TRUSTED_PROXY_HOPS));
+            match forwarded.or_else(|| remote.map(|r| r.ip())) {
+                Some(ip) => ip.to_string(),
+                None => String::from("unknown"),
+            }
+        })
+}
+
+/// The entry `hops` from the right of an `X-Forwarded-For` header, which may carry a port.
+fn forwarded_ip(forwarded: &str, hops: usize) -> Option<IpAddr> {
+    let entry = forwarded.rsplit(',').nth(hops.checked_sub(1)?)?.trim();
+    match entry.parse::<SocketAddr>() {
+        Ok(addr) => Some(addr.ip()),
+        Err(_) => entry.parse::<IpAddr>().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::forwarded_ip;
+
+    #[test]
+    fn forwarded_ip_ignores_entries_set_by_the_client() {
+        let ip = |s: &str| Some(s.parse().unwrap());
+        assert_eq!(forwarded_ip("1.1.1.1, 2.2.2.2", 1), ip("2.2.2.2"));
+        assert_eq!(
+            forwarded_ip("1.1.1.1, 2.2.2.2:5000, 3.3.3.3", 2),
+            ip("2.2.2.2")
+        );
+        assert_eq!(forwarded_ip("[::1]:5000", 1), ip("::1"));
+        assert_eq!(forwarded_ip("2.2.2.2", 2), None);
+        assert_eq!(forwarded_ip("2.2.2.2", 0), None);
+        assert_eq!(forwarded_ip("not an ip", 1), None);
+    }
+}
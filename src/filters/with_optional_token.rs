@@ -1,43 +1,34 @@
+use super::with_request_id;
 use crate::fault::Fault;
 use crate::models::Claims;
-use crate::ACCESS_TOKEN_SECRET;
-use jsonwebtoken::{decode, DecodingKey, Validation}; // errors::ErrorKind
+use crate::token;
+use warp::filters::path::FullPath;
+use warp::http::Method;
 use warp::{reject, Filter, Rejection};
 
 pub fn with_optional_token() -> impl Filter<Extract = (Option<Claims>,), Error = Rejection> + Clone
 {
-    warp::header::optional::<String>("Authorization").and_then(|h: Option<String>| async move {
-        if let Some(h) = h {
-            if h.starts_with("Bearer ") {
-                let g: String = h.chars().skip(7).collect();
+    warp::header::optional::<String>("Authorization")
+        .and(warp::method())
+        .and(warp::path::full())
+        .and(with_request_id())
+        .and_then(
+            |h: Option<String>, method: Method, path: FullPath, request_id: String| async move {
+                if let Some(h) = h {
+                    if h.starts_with("Bearer ") {
+                        let g: String = h.chars().skip(7).collect();
 
-                // Parse.
-                let validation = Validation {
-                    ..Validation::default()
-                };
-                let token_data = match decode::<Claims>(
-                    &g,
-                    &DecodingKey::from_secret(ACCESS_TOKEN_SECRET.as_ref()),
-                    &validation,
-                ) {
-                    Ok(c) => Ok(c),
-                    Err(_) => Err(reject::custom(Fault::Unauthorized)),
-                    // Err(err) => match *err.kind() {
-                    //     ErrorKind::InvalidToken => Err(reject::custom(Fault::Unauthorized)),
-                    //     ErrorKind::ExpiredSignature => Err(reject::custom(Fault::Unauthorized)),
-                    //     _ => Err(reject::custom(Fault::Unauthorized)),
-                    // },
-                };
-
-                match token_data {
-                    Ok(t) => Ok(Some(t.claims)),
-                    Err(err) => Err(err),
+                        // Parse.
+                        let claims =
+                            token::verify_access_token(&g, &method, path.as_str(), &request_id)
+                                .await?;
+                        Ok(Some(claims))
+                    } else {
+                        Err(reject::custom(Fault::Unauthorized))
+                    }
+                } else {
+                    Ok(None)
                 }
-            } else {
-                Err(reject::custom(Fault::Unauthorized))
-            }
-        } else {
-            Ok(None)
-        }
-    })
+            },
+        )
 }
@@ -0,0 +1,31 @@
+use super::with_request_id;
+use crate::fault::Fault;
+use crate::models::Claims;
+use crate::service_account;
+use crate::token;
+use warp::filters::path::FullPath;
+use warp::http::Method;
+use warp::{reject, Filter, Rejection};
+
+/// Like `with_token` but also accepts the API key of a service account, sent as
+/// `Authorization: ApiKey <key>`.
+pub fn with_principal() -> impl Filter<Extract = (Claims,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("Authorization")
+        .and(warp::method())
+        .and(warp::path::full())
+        .and(with_request_id())
+        .and_then(
+            |h: Option<String>, method: Method, path: FullPath, request_id: String| async move {
+                match h {
+                    Some(h) if h.starts_with("Bearer ") => {
+                        token::verify_access_token(&h[7..], &method, path.as_str(), &request_id)
+                            .await
+                    }
+                    Some(h) if h.starts_with("ApiKey ") => {
+                        service_account::authenticate(&h[7..]).await
+                    }
+                    _ => Err(reject::custom(Fault::Unauthorized)),
+                }
+            },
+        )
+}
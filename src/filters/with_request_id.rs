@@ -0,0 +1,22 @@
+use crate::util;
+use warp::{Filter, Rejection};
+
+/// Longest `X-Request-Id` accepted from the client, longer ones are replaced.
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// Extracts the id of the request, the client's `X-Request-Id` if it sent a usable one, otherwise a
+/// new guid.
+pub fn with_request_id() -> impl Filter<Extract = (String,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("X-Request-Id").map(|h: Option<String>| {
+        match h.map(|h| h.trim().to_string()) {
+            Some(h)
+                if !h.is_empty()
+                    && h.len() <= MAX_REQUEST_ID_LENGTH
+                    && h.chars().all(|c| c.is_ascii_graphic()) =>
+            {
+                h
+            }
+            _ => util::new_guid_v4(),
+        }
+    })
+}
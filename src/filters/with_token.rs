@@ -1,37 +1,41 @@
+use super::with_request_id;
 use crate::fault::Fault;
 use crate::models::Claims;
-use crate::ACCESS_TOKEN_SECRET;
-use jsonwebtoken::{decode, DecodingKey, Validation};
+use crate::token;
+use warp::filters::path::FullPath;
+use warp::http::Method;
 use warp::{reject, Filter, Rejection};
 
 pub fn with_token() -> impl Filter<Extract = (Claims,), Error = Rejection> + Clone {
-    warp::header::optional::<String>("Authorization").and_then(|h: Option<String>| async move {
-        if let Some(h) = h {
-            if h.starts_with("Bearer ") {
-                let g: String = h.chars().skip(7).collect();
+    with_token_and_request_id().map(|claims: Claims, _request_id: String| claims)
+}
 
-                // Parse.
-                let validation = Validation {
-                    ..Validation::default()
-                };
-                let token_data = match decode::<Claims>(
-                    &g,
-                    &DecodingKey::from_secret(ACCESS_TOKEN_SECRET.as_ref()),
-                    &validation,
-                ) {
-                    Ok(c) => Ok(c),
-                    Err(_) => Err(reject::custom(Fault::Unauthorized)),
-                };
+/// Like `with_token` but also extracts the id of the request, the same id an impersonated request
+/// is audited under.
+pub fn with_token_and_request_id(
+) -> impl Filter<Extract = (Claims, String), Error = Rejection> + Clone {
+    warp::header::optional::<String>("Authorization")
+        .and(warp::method())
+        .and(warp::path::full())
+        .and(with_request_id())
+        .and_then(
+            |h: Option<String>, method: Method, path: FullPath, request_id: String| async move {
+                if let Some(h) = h {
+                    if h.starts_with("Bearer ") {
+                        let g: String = h.chars().skip(7).collect();
 
-                match token_data {
-                    Ok(t) => Ok(t.claims),
-                    Err(err) => Err(err),
+                        // Parse.
+                        let claims =
+                            token::verify_access_token(&g, &method, path.as_str(), &request_id)
+                                .await?;
+                        Ok((claims, request_id))
+                    } else {
+                        Err(reject::custom(Fault::Unauthorized))
+                    }
+                } else {
+                    Err(reject::custom(Fault::Unauthorized))
                 }
-            } else {
-                Err(reject::custom(Fault::Unauthorized))
-            }
-        } else {
-            Err(reject::custom(Fault::Unauthorized))
-        }
-    })
+            },
+        )
+        .untuple_one()
 }
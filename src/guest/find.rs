@@ -0,0 +1,20 @@
+use crate::models::{Claims, User};
+use crate::policy::{self, Resource};
+use crate::USER_COLLECTION;
+use cosmos_utils::get;
+
+/// The guest user and its etag if the claims belong to a guest, used to upgrade the guest in place
+/// instead of creating a new user. An impersonation token can not upgrade the guest.
+pub async fn find(claims: Option<&Claims>) -> Result<Option<(User, String)>, warp::Rejection> {
+    let claims = match claims {
+        Some(claims) => claims,
+        None => return Ok(None),
+    };
+    let (user, etag): (User, _) = get(USER_COLLECTION, [&claims.sub], &claims.sub).await?;
+    if user.guest && !user.deleted {
+        policy::authorize(claims, policy::GUEST_UPGRADE, &Resource::owner(&user.id))?;
+        Ok(Some((user, etag)))
+    } else {
+        Ok(None)
+    }
+}
@@ -0,0 +1,21 @@
+use crate::models::User;
+use crate::util::log;
+use crate::GUEST_DEVICE_COLLECTION;
+use cosmos_utils::{delete, CosmosErrorKind};
+
+/// Removes the link from the guest's device to the guest, so that the device gets a new guest
+/// next time. Best effort, a stale link is ignored since it points to a user that is no longer a
+/// guest.
+pub async fn forget_device(user: &User) {
+    if let Some(device_id) = &user.guest_device {
+        if let Err(e) = delete(GUEST_DEVICE_COLLECTION, [device_id], device_id, None).await {
+            match e.kind {
+                CosmosErrorKind::NotFound => (),
+                _ => log(format!(
+                    "Could not forget guest device of {} due to {}",
+                    user.id, e
+                )),
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+mod find;
+pub use find::find;
+
+mod forget_device;
+pub use forget_device::forget_device;
+
+mod remove;
+pub use remove::remove;
@@ -0,0 +1,38 @@
+use super::forget_device;
+use crate::models::{EpisodeMetadata, RefreshTokenFamily, SeriesUserData, User};
+use crate::{
+    EPISODE_METADATA_COLLECTION, REFRESH_TOKEN_COLLECTION, SERIES_USER_DATA_COLLECTION,
+    USER_COLLECTION,
+};
+use cosmos_utils::{delete, query_crosspartition};
+
+/// Hard deletes a guest along with its listening data and sessions. Guests have no email or
+/// other personal data, so nothing is kept.
+pub async fn remove(user: &User, etag: String) -> Result<(), warp::Rejection> {
+    // NOTE: The user goes first with its etag, so that a guest that is upgraded or used meanwhile
+    // is left alone.
+    delete(USER_COLLECTION, [&user.id], &user.id, Some(etag)).await?;
+    forget_device(user).await;
+
+    let q = format!("SELECT * FROM {} m", EPISODE_METADATA_COLLECTION);
+    let metadata: Vec<EpisodeMetadata> =
+        query_crosspartition(EPISODE_METADATA_COLLECTION, [&user.id], q, -1, false).await?;
+    for m in metadata {
+        delete(EPISODE_METADATA_COLLECTION, [&user.id], &m.id, None).await?;
+    }
+
+    let q = format!("SELECT * FROM {} d", SERIES_USER_DATA_COLLECTION);
+    let user_data: Vec<SeriesUserData> =
+        query_crosspartition(SERIES_USER_DATA_COLLECTION, [&user.id], q, -1, false).await?;
+    for d in user_data {
+        delete(SERIES_USER_DATA_COLLECTION, [&user.id], &d.id, None).await?;
+    }
+
+    let q = format!("SELECT * FROM {} f", REFRESH_TOKEN_COLLECTION);
+    let families: Vec<RefreshTokenFamily> =
+        query_crosspartition(REFRESH_TOKEN_COLLECTION, [&user.id], q, -1, false).await?;
+    for f in families {
+        delete(REFRESH_TOKEN_COLLECTION, [&user.id], &f.id, None).await?;
+    }
+    Ok(())
+}
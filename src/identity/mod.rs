@@ -0,0 +1,23 @@
+/// An account at an external identity provider, as asserted by a token the provider signed.
+#[derive(Debug, Clone)]
+pub struct ExternalIdentity {
+    pub issuer: String,
+
+    pub subject: String,
+
+    // Only set if the provider has verified the email. Normalised to lowercase.
+    pub email: Option<String>,
+
+    // Whether `email` is a relay address handed out by the provider, e.g. Apple's "Hide My Email".
+    pub private_email: bool,
+
+    pub first_name: Option<String>,
+
+    pub last_name: Option<String>,
+}
+
+mod provider;
+pub use provider::{Provider, OIDC_PROVIDERS};
+
+mod resolve;
+pub use resolve::{link, resolve};
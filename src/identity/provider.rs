@@ -0,0 +1,142 @@
+use super::ExternalIdentity;
+use crate::fault::Fault;
+use crate::util::log;
+use crate::{
+    APPLE_SIGNIN_CLIENT_ID, APPLE_SIGNIN_KID, APPLE_SIGNIN_PRIVATE_KEY, APPLE_SIGNIN_TEAM_ID,
+    APPLE_SIGNIN_URL, GOOGLE_SIGNIN_CLIENT_IDS, OIDC_PROVIDERS_CONFIG,
+};
+use lazy_static::lazy_static;
+use platform_signin::{Client, OidcClient};
+use serde::Deserialize;
+use warp::reject;
+
+const GOOGLE_ISSUER: &str = "https://accounts.google.com";
+const GOOGLE_JWKS_URI: &str = "https://www.googleapis.com/oauth2/v3/certs";
+
+/// An OpenID Connect provider users may sign in with.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcProvider {
+    // Name of the provider in urls, e.g. "google".
+    pub name: String,
+
+    // Canonical issuer, the one linked identities are stored under.
+    pub issuer: String,
+
+    // Other spellings of the issuer the provider puts in its tokens.
+    #[serde(default)]
+    pub issuer_aliases: Vec<String>,
+
+    // Our client ids at the provider, the accepted audiences.
+    pub client_ids: Vec<String>,
+
+    // Looked up through the issuer's discovery document if not set.
+    #[serde(default)]
+    pub jwks_uri: Option<String>,
+}
+
+lazy_static! {
+    pub static ref OIDC_PROVIDERS: Vec<OidcProvider> = {
+        let mut providers: Vec<OidcProvider> = serde_json::from_str(&OIDC_PROVIDERS_CONFIG)
+            .expect("OIDC_PROVIDERS is not a JSON array of providers");
+        if let Some(client_ids) = &*GOOGLE_SIGNIN_CLIENT_IDS {
+            providers.push(OidcProvider {
+                name: String::from("google"),
+                issuer: GOOGLE_ISSUER.to_string(),
+                issuer_aliases: vec![String::from("accounts.google.com")],
+                client_ids: client_ids
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .collect(),
+                jwks_uri: Some(GOOGLE_JWKS_URI.to_string()),
+            });
+        }
+        providers
+    };
+}
+
+pub enum Provider {
+    Apple,
+    Oidc(&'static OidcProvider),
+}
+
+impl Provider {
+    /// Finds a provider by its name in urls.
+    pub fn find(name: &str) -> Option<Provider> {
+        if name == "apple" {
+            return Some(Provider::Apple);
+        }
+        OIDC_PROVIDERS
+            .iter()
+            .find(|p| p.name == name)
+            .map(Provider::Oidc)
+    }
+
+    /// Verifies a credential from the app and returns the identity it asserts. The credential is
+    /// an authorization code for Apple and an id token for OpenID Connect providers.
+    pub async fn verify(
+        &self,
+        credential: &str,
+        nonce: Option<&str>,
+    ) -> Result<ExternalIdentity, warp::Rejection> {
+        match self {
+            Provider::Apple => {
+                let client = Client::new(
+                    APPLE_SIGNIN_CLIENT_ID.to_string(),
+                    APPLE_SIGNIN_PRIVATE_KEY.to_string(),
+                    APPLE_SIGNIN_KID.to_string(),
+                    APPLE_SIGNIN_TEAM_ID.to_string(),
+                    APPLE_SIGNIN_URL.to_string(),
+                )
+                .map_err(|e| {
+                    reject::custom(Fault::Unspecified(format!(
+                        "Could not create Apple signin client: {:?}",
+                        e
+                    )))
+                })?;
+                let id_token = client.authenticate(credential, nonce).await.map_err(|e| {
+                    log(format!("Apple signin failed: {:?}", e));
+                    reject::custom(Fault::Unauthorized)
+                })?;
+                // NOTE: Apple only shares the user's name with the app.
+                Ok(ExternalIdentity {
+                    email: id_token.verified_email().map(|e| e.to_lowercase()),
+                    private_email: id_token.is_private_email,
+                    issuer: id_token.iss,
+                    subject: id_token.sub,
+                    first_name: None,
+                    last_name: None,
+                })
+            }
+            Provider::Oidc(provider) => {
+                let mut issuers = vec![provider.issuer.clone()];
+                issuers.extend(provider.issuer_aliases.iter().cloned());
+                let client = match &provider.jwks_uri {
+                    Some(jwks_uri) => {
+                        OidcClient::new(issuers, provider.client_ids.clone(), jwks_uri.clone())
+                    }
+                    None => OidcClient::discover(issuers, provider.client_ids.clone()).await,
+                }
+                .map_err(|e| {
+                    reject::custom(Fault::Unspecified(format!(
+                        "Could not create {} signin client: {:?}",
+                        provider.name, e
+                    )))
+                })?;
+                let id_token = client.verify(credential, nonce).await.map_err(|e| {
+                    log(format!("{} signin failed: {:?}", provider.name, e));
+                    reject::custom(Fault::Unauthorized)
+                })?;
+                Ok(ExternalIdentity {
+                    // Tokens carrying an alias are stored under the canonical issuer.
+                    issuer: provider.issuer.clone(),
+                    email: id_token.verified_email().map(|e| e.to_lowercase()),
+                    private_email: false,
+                    subject: id_token.sub,
+                    first_name: id_token.given_name,
+                    last_name: id_token.family_name,
+                })
+            }
+        }
+    }
+}
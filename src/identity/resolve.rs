@@ -0,0 +1,217 @@
+use super::ExternalIdentity;
+use crate::fault::Fault;
+use crate::models::{AuthEmail, LinkedIdentity, User};
+use crate::{guest, token, util};
+use crate::{
+    AUTH_EMAIL_COLLECTION, DEFAULT_OFFICE_ID, LINKED_IDENTITY_COLLECTION, PRODUCTION_ENVIRONMENT,
+    USER_COLLECTION,
+};
+use chrono::Utc;
+use cosmos_utils::{delete, get, insert, upsert, CosmosErrorKind};
+use std::collections::HashMap;
+use warp::reject;
+
+/// Returns the id of the user the identity signs in as. An identity seen for the first time is
+/// linked to the user owning its verified email, or to a new user if there is none. The new user is
+/// the given guest upgraded in place, if any. A user whose email is not verified is secured before
+/// the identity is linked, see `secure_unverified`.
+pub async fn resolve(
+    identity: &ExternalIdentity,
+    guest: Option<(User, String)>,
+) -> Result<String, warp::Rejection> {
+    let identity_id = LinkedIdentity::id_for(&identity.issuer, &identity.subject);
+    match get(LINKED_IDENTITY_COLLECTION, [&identity_id], &identity_id).await {
+        Ok((linked, _)) => {
+            let linked: LinkedIdentity = linked;
+            return Ok(linked.user_id);
+        }
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => (),
+            _ => return Err(e.into()),
+        },
+    }
+
+    let email = match &identity.email {
+        Some(email) => email,
+        None => {
+            return Err(reject::custom(Fault::Forbidden(String::from(
+                "The identity provider did not share a verified email",
+            ))))
+        }
+    };
+    let user_id = match get(AUTH_EMAIL_COLLECTION, [email], email).await {
+        Ok((auth_email, etag)) => secure_unverified(auth_email, etag).await?,
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => create_user(email, identity, guest).await?,
+            _ => return Err(e.into()),
+        },
+    };
+    link(identity, &user_id).await?;
+    Ok(user_id)
+}
+
+/// Links the identity to the user. Linking an identity twice to the same user is a no-op, linking
+/// an identity that belongs to another user fails.
+pub async fn link(
+    identity: &ExternalIdentity,
+    user_id: &str,
+) -> Result<LinkedIdentity, warp::Rejection> {
+    let linked = LinkedIdentity {
+        id: LinkedIdentity::id_for(&identity.issuer, &identity.subject),
+        issuer: identity.issuer.clone(),
+        subject: identity.subject.clone(),
+        user_id: user_id.to_string(),
+        email: identity.email.clone(),
+        // NOTE: Private relay addresses are stable per app and forward to the user's real inbox,
+        // so they are used like any other email.
+        private_email: identity.private_email,
+        modified: Utc::now(),
+        created: Utc::now(),
+    };
+    match insert(LINKED_IDENTITY_COLLECTION, [&linked.id], &linked, None).await {
+        Ok(_) => Ok(linked),
+        Err(e) => match e.kind {
+            CosmosErrorKind::Conflict => {
+                let (existing, _): (LinkedIdentity, _) =
+                    get(LINKED_IDENTITY_COLLECTION, [&linked.id], &linked.id).await?;
+                if existing.user_id == user_id {
+                    Ok(existing)
+                } else {
+                    Err(reject::custom(Fault::Duplicate(String::from(
+                        "The identity is already linked to another user",
+                    ))))
+                }
+            }
+            _ => Err(e.into()),
+        },
+    }
+}
+
+/// The provider has verified the email while the user owning it has not, so whoever registered the
+/// email may not own it. Their password, second factor and sessions are dropped, and the email is
+/// marked verified, before the identity is linked. Returns the user's id.
+async fn secure_unverified(
+    mut auth_email: AuthEmail,
+    etag: String,
+) -> Result<String, warp::Rejection> {
+    let (mut user, user_etag): (User, _) =
+        get(USER_COLLECTION, [&auth_email.user_id], &auth_email.user_id).await?;
+    if user.email_verified_at.is_some() {
+        return Ok(user.id);
+    }
+
+    auth_email.passhash = String::new();
+    auth_email.two_factor = None;
+    upsert(
+        AUTH_EMAIL_COLLECTION,
+        [&auth_email.id],
+        &auth_email,
+        Some(&etag),
+    )
+    .await?;
+    user.email_verified_at = Some(Utc::now());
+    user.modified = Utc::now();
+    upsert(USER_COLLECTION, [&user.id], &user, Some(&user_etag)).await?;
+    token::revoke_all(&user.id).await?;
+    Ok(user.id)
+}
+
+async fn create_user(
+    email: &str,
+    identity: &ExternalIdentity,
+    guest: Option<(User, String)>,
+) -> Result<String, warp::Rejection> {
+    let first_name = identity.first_name.clone().unwrap_or_default();
+    if let Some((guest, etag)) = guest {
+        return upgrade_guest(email, identity, first_name, guest, etag).await;
+    }
+    let user = User {
+        id: util::new_guid_v4(),
+        deleted: false,
+        // Automatically make user a test user if on the test server.
+        test: !*PRODUCTION_ENVIRONMENT,
+        guest: false,
+        guest_device: None,
+        roles: vec![],
+        devices: vec![],
+        images: vec![],
+        preferred_name: Some(first_name.clone()),
+        last_name: identity.last_name.clone().unwrap_or_default(),
+        middle_names: None,
+        first_name,
+        office_ids: vec![DEFAULT_OFFICE_ID.to_string()],
+        office_joined_at: HashMap::new(),
+        deleted_office_ids: vec![],
+        saved_series: vec![],
+        email: email.to_string(),
+        phone: None,
+        favourite_episode_ids: vec![],
+        // The provider has verified the email.
+        email_verified_at: Some(Utc::now()),
+        email_verification_sent_at: None,
+        pending_email: None,
+        token_generation: 0,
+        role_generation: 0,
+        modified: Utc::now(),
+        created: Utc::now(),
+    };
+    let user_etag = insert(USER_COLLECTION, [&user.id], &user, None).await?;
+
+    // The empty passhash never verifies, so the user has to set a password through forgot
+    // password before being able to sign in with email.
+    let email_auth = AuthEmail {
+        id: email.to_string(),
+        passhash: String::new(),
+        user_id: user.id.clone(),
+        two_factor: None,
+    };
+    match insert(AUTH_EMAIL_COLLECTION, [&email_auth.id], &email_auth, None).await {
+        Ok(_) => (),
+        Err(e) => {
+            // NOTE: Error here should hard delete the previously inserted document.
+            delete(USER_COLLECTION, [&user.id], &user.id, Some(user_etag)).await?;
+            return Err(e.into());
+        }
+    }
+    Ok(user.id)
+}
+
+async fn upgrade_guest(
+    email: &str,
+    identity: &ExternalIdentity,
+    first_name: String,
+    guest: User,
+    etag: String,
+) -> Result<String, warp::Rejection> {
+    let mut user = guest.clone();
+    user.guest = false;
+    user.guest_device = None;
+    user.preferred_name = Some(first_name.clone());
+    user.last_name = identity.last_name.clone().unwrap_or_default();
+    user.first_name = first_name;
+    user.email = email.to_string();
+    // The provider has verified the email.
+    user.email_verified_at = Some(Utc::now());
+    user.modified = Utc::now();
+    let user_etag = upsert(USER_COLLECTION, [&user.id], &user, Some(&etag)).await?;
+
+    let email_auth = AuthEmail {
+        id: email.to_string(),
+        passhash: String::new(),
+        user_id: user.id.clone(),
+        two_factor: None,
+    };
+    match insert(AUTH_EMAIL_COLLECTION, [&email_auth.id], &email_auth, None).await {
+        Ok(_) => (),
+        Err(e) => {
+            // The upgrade is undone so the guest keeps working.
+            upsert(USER_COLLECTION, [&user.id], &guest, Some(&user_etag)).await?;
+            return Err(e.into());
+        }
+    }
+    guest::forget_device(&guest).await;
+    // The device id was the guest's only credential, so every session of the guest is revoked
+    // and the caller signs in anew as the upgraded user.
+    token::revoke_all(&user.id).await?;
+    Ok(user.id)
+}
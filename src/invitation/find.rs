@@ -0,0 +1,30 @@
+use super::TOKEN_SEPARATOR;
+use crate::fault::Fault;
+use crate::models::Invitation;
+use crate::util;
+use crate::INVITATION_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::{get, CosmosErrorKind};
+use warp::reject;
+
+/// Resolves an accept token to its invitation and the invitation's etag. Fails with
+/// `Fault::Unauthorized` if the token is unknown, replaced by a resend or expired.
+pub async fn find(token: &str) -> Result<(Invitation, String), warp::Rejection> {
+    let (invitation_id, secret) = token
+        .split_once(TOKEN_SEPARATOR)
+        .ok_or_else(|| reject::custom(Fault::Unauthorized))?;
+
+    let (invitation, etag): (Invitation, _) =
+        match get(INVITATION_COLLECTION, [invitation_id], invitation_id).await {
+            Ok(i) => i,
+            Err(e) => match e.kind {
+                CosmosErrorKind::NotFound => return Err(reject::custom(Fault::Unauthorized)),
+                _ => return Err(e.into()),
+            },
+        };
+
+    if invitation.expires < Utc::now() || invitation.secret_hash != util::hash_token(secret) {
+        return Err(reject::custom(Fault::Unauthorized));
+    }
+    Ok((invitation, etag))
+}
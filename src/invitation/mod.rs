@@ -0,0 +1,14 @@
+/// Separates the invitation id from the secret in an accept token.
+const TOKEN_SEPARATOR: char = '.';
+
+/// Length of the secret part of an accept token.
+const SECRET_LENGTH: usize = 40;
+
+/// How long an invitation can be accepted, counted from when it was last sent.
+const LIFETIME_DAYS: i64 = 14;
+
+mod send;
+pub use send::send;
+
+mod find;
+pub use find::find;
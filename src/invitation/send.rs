@@ -0,0 +1,29 @@
+use super::{LIFETIME_DAYS, SECRET_LENGTH, TOKEN_SEPARATOR};
+use crate::email;
+use crate::models::Invitation;
+use crate::util;
+use crate::INVITATION_COLLECTION;
+use chrono::{Duration, Utc};
+use cosmos_utils::upsert;
+
+/// Gives the invitation a new accept token and expiry, stores it and emails the token to the
+/// invited address. Tokens sent earlier stop working. Only the hash of the token is stored, and it
+/// is cleared from the returned invitation.
+pub async fn send(
+    mut invitation: Invitation,
+    etag: Option<&str>,
+) -> Result<Invitation, warp::Rejection> {
+    let secret = util::random_string(SECRET_LENGTH);
+    let now = Utc::now();
+    invitation.secret_hash = util::hash_token(&secret);
+    invitation.expires = now + Duration::days(LIFETIME_DAYS);
+    invitation.sent = now;
+    invitation.modified = now;
+    upsert(INVITATION_COLLECTION, [&invitation.id], &invitation, etag).await?;
+
+    let token = format!("{}{}{}", invitation.id, TOKEN_SEPARATOR, secret);
+    email::send_invitation(&invitation.email, &token).await?;
+
+    invitation.secret_hash = String::new();
+    Ok(invitation)
+}
@@ -2,16 +2,32 @@
 #![recursion_limit = "256"]
 #![type_length_limit = "2000000"]
 use appinsights::{InMemoryChannel, TelemetryClient, TelemetryConfig};
+use chrono::{DateTime, Utc};
 use cosmos_utils::{set_state, CosmosState};
 use lazy_static::lazy_static;
 use std::time::Duration;
 use warp::{http::Method, Filter};
 mod api;
+mod audit;
+mod email;
 mod models;
 use models::*;
 mod fault;
 mod filters;
+mod guest;
+mod identity;
+mod invitation;
+mod membership;
+mod office_deletion;
+mod password;
+mod policy;
+mod product_catalog;
 mod push;
+mod roles;
+mod service_account;
+mod throttle;
+mod token;
+mod two_factor;
 mod util;
 #[macro_use]
 extern crate bitflags;
@@ -116,15 +132,106 @@ lazy_static! {
     static ref APPLE_SIGNIN_CLIENT_ID: String = std::env::var("APPLE_SIGNIN_CLIENT_ID").unwrap();
     static ref APPLE_SIGNIN_PRIVATE_KEY: String =
         std::env::var("APPLE_SIGNIN_PRIVATE_KEY").unwrap();
-    static ref CRON_SECRET: String = std::env::var("CRON_SECRET").unwrap();
+    // Base url of Apple's identity service, overridable to test against a stub.
+    static ref APPLE_SIGNIN_URL: String = std::env::var("APPLE_SIGNIN_URL")
+        .unwrap_or_else(|_| platform_signin::APPLE_SERVER.to_string());
+    // Comma separated client ids of the apps at Google, Google signin is disabled if unset.
+    static ref GOOGLE_SIGNIN_CLIENT_IDS: Option<String> =
+        std::env::var("GOOGLE_SIGNIN_CLIENT_IDS").ok();
+    // JSON array of further OpenID Connect providers, see `OidcProvider` in the identity module.
+    static ref OIDC_PROVIDERS_CONFIG: String =
+        std::env::var("OIDC_PROVIDERS").unwrap_or_else(|_| String::from("[]"));
+    // Page the link in verification emails leads to, the token is appended as a query parameter.
+    static ref EMAIL_VERIFICATION_URL: String = std::env::var("EMAIL_VERIFICATION_URL").unwrap();
+    static ref EMAIL_VERIFICATION_TEMPLATE_ID: String =
+        std::env::var("SENDGRID_EMAIL_VERIFICATION_TEMPLATE_ID").unwrap();
+    // Page the link confirming a change of email leads to, the token is appended as a query
+    // parameter.
+    static ref EMAIL_CHANGE_URL: String = std::env::var("EMAIL_CHANGE_URL").unwrap();
+    static ref EMAIL_CHANGE_TEMPLATE_ID: String =
+        std::env::var("SENDGRID_EMAIL_CHANGE_TEMPLATE_ID").unwrap();
+    // Page the link in passwordless signin emails leads to, the email and token are appended as
+    // query parameters.
+    static ref SIGNIN_LINK_URL: String = std::env::var("SIGNIN_LINK_URL").unwrap();
+    static ref SIGNIN_LINK_TEMPLATE_ID: String =
+        std::env::var("SENDGRID_SIGNIN_LINK_TEMPLATE_ID").unwrap();
+    // Page the link in forgot password emails leads to, the token is appended as a query
+    // parameter.
+    static ref PASSWORD_RESET_URL: String = std::env::var("PASSWORD_RESET_URL").unwrap();
+    static ref PASSWORD_RESET_TEMPLATE_ID: String =
+        std::env::var("SENDGRID_PASSWORD_RESET_TEMPLATE_ID").unwrap();
+    // Page the link in office invitation emails leads to, the accept token is appended as a query
+    // parameter.
+    static ref INVITATION_URL: String = std::env::var("INVITATION_URL").unwrap();
+    static ref INVITATION_TEMPLATE_ID: String =
+        std::env::var("SENDGRID_INVITATION_TEMPLATE_ID").unwrap();
+    // JSON password policy, see `PasswordPolicy` in the password module for the defaults.
+    static ref PASSWORD_POLICY: Option<String> = std::env::var("PASSWORD_POLICY").ok();
+    // JSON Argon2id parameters for password hashes, see `HashParams` in the password module.
+    static ref PASSWORD_HASH_PARAMS: Option<String> = std::env::var("PASSWORD_HASH_PARAMS").ok();
+    // Path of the breached password list, no passwords are screened if unset.
+    static ref BREACHED_PASSWORDS_FILE: Option<String> =
+        std::env::var("BREACHED_PASSWORDS_FILE").ok();
+    // Base64 key the TOTP secrets are encrypted with.
+    static ref TWO_FACTOR_ENCRYPTION_KEY: String =
+        std::env::var("TWO_FACTOR_ENCRYPTION_KEY").unwrap();
+    // Whether every admin role requires signing in with a second factor, offices can also require
+    // it for their own admins.
+    static ref REQUIRE_TWO_FACTOR_FOR_ADMINS: bool =
+        std::env::var("REQUIRE_TWO_FACTOR_FOR_ADMINS").unwrap_or_default() == "true";
+    // Number of trusted proxies in front of the api that append to `X-Forwarded-For`, the client
+    // ip is the entry this many places from the right.
+    static ref TRUSTED_PROXY_HOPS: usize = std::env::var("TRUSTED_PROXY_HOPS")
+        .ok()
+        .and_then(|h| h.parse().ok())
+        .unwrap_or(1);
+    // Guests that have not refreshed a session for this many days are removed.
+    static ref GUEST_INACTIVITY_DAYS: i64 = std::env::var("GUEST_INACTIVITY_DAYS")
+        .ok()
+        .and_then(|d| d.parse().ok())
+        .unwrap_or(90);
+    // Users created since this time can only purchase subscriptions once their email is
+    // verified, users created before email verification existed are exempt. Not required if
+    // unset.
+    static ref REQUIRE_VERIFIED_EMAIL_FOR_PURCHASE_SINCE: Option<DateTime<Utc>> =
+        std::env::var("REQUIRE_VERIFIED_EMAIL_FOR_PURCHASE_SINCE").ok().map(|s| {
+            DateTime::parse_from_rfc3339(&s)
+                .unwrap()
+                .with_timezone(&Utc)
+        });
+    static ref TOKEN_SIGNING_KEYS: String = std::env::var("TOKEN_SIGNING_KEYS").unwrap();
+    static ref TOKEN_SIGNING_KID: String = std::env::var("TOKEN_SIGNING_KID").unwrap();
+    // HS256 tokens signed with the shared secrets are accepted until this time, or indefinitely
+    // if unset.
+    static ref HS256_ACCEPTED_UNTIL: Option<DateTime<Utc>> =
+        std::env::var("HS256_ACCEPTED_UNTIL").ok().map(|s| {
+            DateTime::parse_from_rfc3339(&s)
+                .unwrap()
+                .with_timezone(&Utc)
+        });
 }
 
 const RECORDINGS_STORAGE_CONTAINER: &str = "episodes/recordings";
 const SERIES_IMAGE_STORAGE_CONTAINER: &str = "series-images";
 const EPISODE_IMAGE_STORAGE_CONTAINER: &str = "episode-images";
+const OFFICE_IMAGE_STORAGE_CONTAINER: &str = "office-images";
 
 const USER_COLLECTION: &str = "users";
 const AUTH_EMAIL_COLLECTION: &str = "auth_emails";
+const REFRESH_TOKEN_COLLECTION: &str = "refresh_tokens";
+const LINKED_IDENTITY_COLLECTION: &str = "linked_identities";
+const PASSWORD_RESET_COLLECTION: &str = "password_resets";
+const SIGNIN_ATTEMPT_COLLECTION: &str = "signin_attempts";
+const SIGNIN_CODE_COLLECTION: &str = "signin_codes";
+const SERVICE_ACCOUNT_COLLECTION: &str = "service_accounts";
+const GUEST_DEVICE_COLLECTION: &str = "guest_devices";
+const INVITATION_COLLECTION: &str = "invitations";
+const AUDIT_COLLECTION: &str = "audit";
+const JOIN_CODE_COLLECTION: &str = "join_codes";
+const OFFICE_SETTINGS_COLLECTION: &str = "office_settings";
+const OFFICE_DELETION_COLLECTION: &str = "office_deletions";
+const PRODUCT_COLLECTION: &str = "products";
+const PRODUCT_KEY_COLLECTION: &str = "product_keys";
 const EPISODE_COLLECTION: &str = "episodes";
 const SERIES_COLLECTION: &str = "series";
 const SERIES_USER_DATA_COLLECTION: &str = "series_user_data";
@@ -143,7 +250,11 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
     let series_user_data = warp::path("series_user_data");
     let episode_metadata = warp::path("episode_metadata");
     let password = warp::path("password");
+    let email = warp::path("email");
     let recommendations = warp::path("recommendations");
+    let invitations = warp::path("invitations");
+    let join_codes = warp::path("join_codes");
+    let products = warp::path("products");
 
     let cors = warp::cors()
         .allow_any_origin()
@@ -179,9 +290,36 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::param())
         .and(warp::path::end())
         .and(warp::delete())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
         .and_then(api::user_delete));
+    let user_impersonate = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("impersonate"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
+        .and_then(api::user_impersonate));
+    let user_office_put = maybe_box!(users
+        .and(warp::path::param())
+        .and(offices)
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
+        .and_then(api::user_office_put));
+    let user_office_delete = maybe_box!(users
+        .and(warp::path::param())
+        .and(offices)
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::delete())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
+        .and_then(api::user_office_delete));
     let user_put = maybe_box!(users
         .and(warp::path::param())
         .and(warp::path::end())
@@ -204,15 +342,63 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::end())
         .and(warp::post())
         .and(warp::body::json())
+        .and(filters::with_optional_token())
+        .and(filters::with_client_ip())
         .and(filters::with_version())
         .and_then(api::signup));
+    let guest_signin = maybe_box!(users
+        .and(warp::path("guest"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_client_ip())
+        .and(filters::with_version())
+        .and_then(api::guest_signin));
     let signin = maybe_box!(users
         .and(warp::path("signin"))
         .and(warp::path::end())
         .and(warp::post())
         .and(warp::body::json())
+        .and(filters::with_client_ip())
         .and(filters::with_version())
         .and_then(api::signin));
+    let signin_two_factor = maybe_box!(users
+        .and(warp::path("signin"))
+        .and(warp::path("2fa"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_client_ip())
+        .and(filters::with_version())
+        .and_then(api::signin_two_factor));
+    let request_signin_code = maybe_box!(users
+        .and(warp::path("signin"))
+        .and(warp::path("code"))
+        .and(warp::path("request"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_version())
+        .and_then(api::request_signin_code));
+    let signin_code = maybe_box!(users
+        .and(warp::path("signin"))
+        .and(warp::path("code"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_client_ip())
+        .and(filters::with_version())
+        .and_then(api::signin_code));
+    let signin_provider = maybe_box!(users
+        .and(warp::path("signin"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_optional_token())
+        .and(filters::with_client_ip())
+        .and(filters::with_version())
+        .and_then(api::signin_provider));
     let refresh_token = maybe_box!(users
         .and(warp::path::param())
         .and(warp::path("token"))
@@ -220,8 +406,70 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::end())
         .and(warp::post())
         .and(warp::body::json())
+        .and(filters::with_client_ip())
         .and(filters::with_version())
         .and_then(api::refresh_token));
+    let signout = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("signout"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::signout));
+    let signout_all = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("signout"))
+        .and(warp::path("all"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::signout_all));
+    let user_sessions_get = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("sessions"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::user_sessions_get));
+    let user_session_delete = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("sessions"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::delete())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::user_session_delete));
+    let user_identity_post = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("identities"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::user_identity_post));
+    let user_identities_get = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("identities"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::user_identities_get));
+    let user_identity_delete = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("identities"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::delete())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::user_identity_delete));
     let user_poll = maybe_box!(users
         .and(warp::path::param())
         .and(warp::path("poll"))
@@ -238,26 +486,119 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::end())
         .and(warp::put())
         .and(warp::body::json())
-        .and(filters::with_optional_token())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_client_ip())
         .and(filters::with_version())
         .and_then(api::change_password));
+    let two_factor_post = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("2fa"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::two_factor_post));
+    let two_factor_confirm = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("2fa"))
+        .and(warp::path("confirm"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::two_factor_confirm));
+    let two_factor_disable = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("2fa"))
+        .and(warp::path("disable"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::two_factor_disable));
     let forgot_password = maybe_box!(users
         .and(password)
         .and(warp::path("forgot"))
         .and(warp::path::end())
         .and(warp::post())
         .and(warp::body::json())
+        .and(filters::with_client_ip())
         .and(filters::with_version())
         .and_then(api::forgot_password));
-    let user_roles_put = maybe_box!(users
+    let reset_password = maybe_box!(users
+        .and(password)
+        .and(warp::path("reset"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_version())
+        .and_then(api::reset_password));
+    let verify_email = maybe_box!(users
+        .and(email)
+        .and(warp::path("verify"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_version())
+        .and_then(api::verify_email));
+    let resend_verification_email = maybe_box!(users
         .and(warp::path::param())
-        .and(warp::path("roles"))
+        .and(email)
+        .and(warp::path("verify"))
+        .and(warp::path("resend"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::resend_verification_email));
+    let user_email_post = maybe_box!(users
+        .and(warp::path::param())
+        .and(email)
         .and(warp::path::end())
         .and(warp::post())
         .and(warp::body::json())
         .and(filters::with_token())
         .and(filters::with_version())
+        .and_then(api::user_email_post));
+    let confirm_email_change = maybe_box!(users
+        .and(email)
+        .and(warp::path("change"))
+        .and(warp::path("confirm"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_version())
+        .and_then(api::confirm_email_change));
+    let user_roles_post = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("roles"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
+        .and_then(api::user_roles_post));
+    let user_roles_put = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("roles"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
         .and_then(api::user_roles_put));
+    let user_roles_revoke = maybe_box!(users
+        .and(warp::path::param())
+        .and(warp::path("roles"))
+        .and(warp::path("revoke"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
+        .and_then(api::user_roles_revoke));
     let user_device_post = maybe_box!(users
         .and(warp::path::param())
         .and(warp::path("devices"))
@@ -271,7 +612,7 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::end())
         .and(warp::post())
         .and(warp::body::json())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
         .and_then(Office::post));
     let office_put = maybe_box!(offices
@@ -279,7 +620,7 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::end())
         .and(warp::put())
         .and(warp::body::json())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
         .and_then(Office::put));
     let office_get = maybe_box!(offices
@@ -293,7 +634,7 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::param())
         .and(warp::path::end())
         .and(warp::delete())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
         .and_then(Office::delete));
     let office_poll = maybe_box!(offices
@@ -306,6 +647,166 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(filters::with_range())
         .and(filters::with_since())
         .and_then(api::office_poll));
+    let office_restore = maybe_box!(offices
+        .and(warp::path::param())
+        .and(warp::path("restore"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
+        .and_then(api::office_restore));
+    let office_deletion_get = maybe_box!(offices
+        .and(warp::path::param())
+        .and(warp::path("deletion"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::office_deletion_get));
+    let office_audit_get = maybe_box!(offices
+        .and(warp::path::param())
+        .and(warp::path("audit"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<AuditQuery>())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and(filters::with_range())
+        .and_then(api::office_audit_get));
+    let office_settings_put = maybe_box!(offices
+        .and(warp::path::param())
+        .and(warp::path("settings"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
+        .and_then(api::office_settings_put));
+    let office_settings_image_put = maybe_box!(offices
+        .and(warp::path::param())
+        .and(warp::path("settings"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
+        .and(warp::body::content_length_limit(1024 * 1000 * 16)) // 16 mb.
+        .and(warp::filters::multipart::form().max_length(1024 * 1000 * 16)) // 16 mb.
+        .and_then(api::office_settings_image_put));
+    let office_products_get = maybe_box!(offices
+        .and(warp::path::param())
+        .and(products)
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::office_products_get));
+    let office_product_post = maybe_box!(offices
+        .and(warp::path::param())
+        .and(products)
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
+        .and_then(api::office_product_post));
+    let office_product_put = maybe_box!(offices
+        .and(warp::path::param())
+        .and(products)
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
+        .and_then(api::office_product_put));
+    let office_product_delete = maybe_box!(offices
+        .and(warp::path::param())
+        .and(products)
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::delete())
+        .and(filters::with_token_and_request_id())
+        .and(filters::with_version())
+        .and_then(api::office_product_delete));
+    let office_join_code_post = maybe_box!(offices
+        .and(warp::path::param())
+        .and(join_codes)
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::office_join_code_post));
+    let office_join_codes_get = maybe_box!(offices
+        .and(warp::path::param())
+        .and(join_codes)
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::office_join_codes_get));
+    let office_join_code_delete = maybe_box!(offices
+        .and(warp::path::param())
+        .and(join_codes)
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::delete())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::office_join_code_delete));
+    let join_code_redeem = maybe_box!(join_codes
+        .and(warp::path("redeem"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::join_code_redeem));
+    let office_invitation_post = maybe_box!(offices
+        .and(warp::path::param())
+        .and(invitations)
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::office_invitation_post));
+    let office_invitations_get = maybe_box!(offices
+        .and(warp::path::param())
+        .and(invitations)
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::office_invitations_get));
+    let office_invitation_resend = maybe_box!(offices
+        .and(warp::path::param())
+        .and(invitations)
+        .and(warp::path::param())
+        .and(warp::path("resend"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::office_invitation_resend));
+    let office_invitation_delete = maybe_box!(offices
+        .and(warp::path::param())
+        .and(invitations)
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::delete())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::office_invitation_delete));
+    let invitation_accept = maybe_box!(invitations
+        .and(warp::path("accept"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::invitation_accept));
     let get_all_offices = maybe_box!(offices
         .and(warp::path::end())
         .and(warp::get())
@@ -319,9 +820,9 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::end())
         .and(warp::post())
         .and(warp::body::json())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
-        .and_then(Series::post));
+        .and_then(api::office_content_post::<Series>));
     let series_put = maybe_box!(offices
         .and(warp::path::param())
         .and(series)
@@ -329,9 +830,9 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::end())
         .and(warp::put())
         .and(warp::body::json())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
-        .and_then(Series::put));
+        .and_then(api::office_content_put::<Series>));
     let series_get = maybe_box!(offices
         .and(warp::path::param())
         .and(series)
@@ -347,9 +848,9 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::param())
         .and(warp::path::end())
         .and(warp::delete())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
-        .and_then(Series::delete));
+        .and_then(api::office_content_delete::<Series>));
     let series_image = maybe_box!(offices
         .and(warp::path::param())
         .and(series)
@@ -357,7 +858,7 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path("image"))
         .and(warp::path::end())
         .and(warp::put())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
         .and(warp::body::content_length_limit(1024 * 1000 * 16)) // 16 mb.
         .and(warp::filters::multipart::form().max_length(1024 * 1000 * 16)) // 16 mb.
@@ -369,7 +870,7 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::post())
         .and(warp::post())
         .and(warp::body::json())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
         .and_then(Episode::post));
     let episode_put = maybe_box!(offices
@@ -379,9 +880,9 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::end())
         .and(warp::put())
         .and(warp::body::json())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
-        .and_then(Episode::put));
+        .and_then(api::office_content_put::<Episode>));
     let episode_get = maybe_box!(offices
         .and(warp::path::param())
         .and(episodes)
@@ -397,9 +898,9 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::param())
         .and(warp::path::end())
         .and(warp::delete())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
-        .and_then(Episode::delete));
+        .and_then(api::office_content_delete::<Episode>));
     let episode_image = maybe_box!(offices
         .and(warp::path::param())
         .and(episodes)
@@ -407,7 +908,7 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path("image"))
         .and(warp::path::end())
         .and(warp::put())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
         .and(warp::body::content_length_limit(1024 * 1000 * 16)) // 16 mb.
         .and(warp::filters::multipart::form().max_length(1024 * 1000 * 16)) // 16 mb.
@@ -419,7 +920,7 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path("sound"))
         .and(warp::path::end())
         .and(warp::put())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
         .and(warp::body::content_length_limit(1024 * 1000 * 750)) // 750 mb.
         .and(warp::filters::multipart::form().max_length(1024 * 1000 * 750)) // 750 mb.
@@ -504,9 +1005,9 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::end())
         .and(warp::post())
         .and(warp::body::json())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
-        .and_then(Recommendation::post));
+        .and_then(api::office_content_post::<Recommendation>));
     let recommended_put = maybe_box!(offices
         .and(warp::path::param())
         .and(recommendations)
@@ -514,9 +1015,9 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::end())
         .and(warp::put())
         .and(warp::body::json())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
-        .and_then(Recommendation::put));
+        .and_then(api::office_content_put::<Recommendation>));
     let recommended_get = maybe_box!(offices
         .and(warp::path::param())
         .and(recommendations)
@@ -532,18 +1033,18 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::param())
         .and(warp::path::end())
         .and(warp::delete())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
-        .and_then(Recommendation::delete));
+        .and_then(api::office_content_delete::<Recommendation>));
     let category_post = maybe_box!(offices
         .and(warp::path::param())
         .and(categories)
         .and(warp::path::end())
         .and(warp::post())
         .and(warp::body::json())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
-        .and_then(Category::post));
+        .and_then(api::office_content_post::<Category>));
     let category_put = maybe_box!(offices
         .and(warp::path::param())
         .and(categories)
@@ -551,9 +1052,9 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::end())
         .and(warp::put())
         .and(warp::body::json())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
-        .and_then(Category::put));
+        .and_then(api::office_content_put::<Category>));
     let category_get = maybe_box!(offices
         .and(warp::path::param())
         .and(categories)
@@ -569,9 +1070,9 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and(warp::path::param())
         .and(warp::path::end())
         .and(warp::delete())
-        .and(filters::with_token())
+        .and(filters::with_token_and_request_id())
         .and(filters::with_version())
-        .and_then(Category::delete));
+        .and_then(api::office_content_delete::<Category>));
 
     let subscriptions = warp::path("subscriptions");
     let subscription_get = users
@@ -598,15 +1099,33 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
     let cron = warp::path("cron")
         .and(warp::path::end())
         .and(warp::post())
-        .and(warp::body::json())
+        .and(filters::with_principal())
         .and(filters::with_version())
         .and_then(api::cron)
         .boxed();
 
+    let office_deletions_resume = warp::path("cron")
+        .and(warp::path("office_deletions"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(filters::with_principal())
+        .and(filters::with_version())
+        .and_then(api::office_deletions_resume)
+        .boxed();
+
+    let guests_cleanup = warp::path("cron")
+        .and(warp::path("guests"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(filters::with_principal())
+        .and(filters::with_version())
+        .and_then(api::guests_cleanup)
+        .boxed();
+
     let users_registered_in_period = warp::path("new_users_email")
         .and(warp::path::end())
         .and(warp::post())
-        .and(warp::body::json())
+        .and(filters::with_principal())
         .and(filters::with_version())
         .and_then(api::new_users_email)
         .boxed();
@@ -621,6 +1140,55 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .and_then(api::webhook_subscription_apple)
         .boxed();
 
+    let service_accounts = warp::path("service_accounts");
+    let service_account_post = maybe_box!(service_accounts
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::service_account_post));
+    let service_accounts_get = maybe_box!(service_accounts
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::service_accounts_get));
+    let service_account_delete = maybe_box!(service_accounts
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::delete())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::service_account_delete));
+    let audit_get = maybe_box!(warp::path("audit")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<AuditQuery>())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and(filters::with_range())
+        .and_then(api::audit_get));
+    let lockouts_get = maybe_box!(warp::path("lockouts")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::lockouts_get));
+    let password_hash_report_get = maybe_box!(warp::path("reports")
+        .and(warp::path("password_hashes"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(filters::with_token())
+        .and(filters::with_version())
+        .and_then(api::password_hash_report_get));
+    let jwks_get = warp::path(".well-known")
+        .and(warp::path("jwks.json"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and_then(api::jwks_get)
+        .boxed();
+
     // Required by Azure health checks.
     let main = warp::path::end().map(|| warp::reply());
 
@@ -630,16 +1198,41 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
     let routes = maybe_box!(main
         .or(user_get)
         .or(user_delete)
+        .or(user_impersonate)
+        .or(user_office_put)
+        .or(user_office_delete)
         .or(user_put)
         .or(user_image_put)
+        .or(user_roles_post)
         .or(user_roles_put)
+        .or(user_roles_revoke)
         .or(user_device_post)
         .or(signin)
+        .or(signin_two_factor)
+        .or(request_signin_code)
+        .or(signin_code)
+        .or(signin_provider)
         .or(signup)
+        .or(guest_signin)
         .or(refresh_token)
+        .or(signout)
+        .or(signout_all)
+        .or(user_sessions_get)
+        .or(user_session_delete)
+        .or(user_identity_post)
+        .or(user_identities_get)
+        .or(user_identity_delete)
         .or(user_poll)
         .or(forgot_password)
+        .or(reset_password)
         .or(change_password)
+        .or(two_factor_post)
+        .or(two_factor_confirm)
+        .or(two_factor_disable)
+        .or(verify_email)
+        .or(resend_verification_email)
+        .or(user_email_post)
+        .or(confirm_email_change)
         .or(series_post)
         .or(series_put)
         .or(series_get)
@@ -665,9 +1258,34 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .or(office_delete)
         .or(office_poll)
         .or(get_all_offices)
+        .or(office_restore)
+        .or(office_deletion_get)
+        .or(office_audit_get)
+        .or(office_settings_put)
+        .or(office_settings_image_put)
+        .or(office_products_get)
+        .or(office_product_post)
+        .or(office_product_put)
+        .or(office_product_delete)
+        .or(office_join_code_post)
+        .or(office_join_codes_get)
+        .or(office_join_code_delete)
+        .or(join_code_redeem)
+        .or(office_invitation_post)
+        .or(office_invitations_get)
+        .or(office_invitation_resend)
+        .or(office_invitation_delete)
+        .or(invitation_accept)
         .or(subscription_post)
         .or(subscription_get)
         .or(webhook_subscription_apple)
+        .or(service_account_post)
+        .or(service_accounts_get)
+        .or(service_account_delete)
+        .or(audit_get)
+        .or(lockouts_get)
+        .or(password_hash_report_get)
+        .or(jwks_get)
         .or(recommended_post)
         .or(recommended_put)
         .or(recommended_get)
@@ -677,6 +1295,8 @@ fn routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection>
         .or(category_get)
         .or(category_delete)
         .or(cron)
+        .or(guests_cleanup)
+        .or(office_deletions_resume)
         .or(users_registered_in_period)
         .or(options)
         .recover(filters::handle_rejection)
@@ -698,6 +1318,14 @@ async fn main() {
     };
     set_state(cosmos_state);
 
+    // Fail at startup rather than on the first request if the token keys are misconfigured.
+    lazy_static::initialize(&token::VERIFICATION_KEYS);
+    lazy_static::initialize(&token::SIGNING_KEY);
+    lazy_static::initialize(&identity::OIDC_PROVIDERS);
+    lazy_static::initialize(&password::POLICY);
+    lazy_static::initialize(&password::HASH_PARAMS);
+    lazy_static::initialize(&password::BREACHED_PASSWORDS);
+
     if cfg!(debug_assertions) {
         warp::serve(routes).run(([127, 0, 0, 1], 3030)).await
     } else {
@@ -0,0 +1,14 @@
+use crate::models::User;
+use chrono::Utc;
+
+/// Makes the user a member of the office, returns false if the user already was.
+pub fn add(user: &mut User, office_id: &str) -> bool {
+    if user.office_ids.iter().any(|o| o == office_id) {
+        return false;
+    }
+    let now = Utc::now();
+    user.office_ids.push(office_id.to_string());
+    user.office_joined_at.insert(office_id.to_string(), now);
+    user.modified = now;
+    true
+}
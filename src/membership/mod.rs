@@ -0,0 +1,14 @@
+//! Which offices a user is a member of. Members see the office's content in their poll, and
+//! office roles are only held by members.
+
+/// Length of a join code, long enough not to be guessed while short enough to be typed.
+const JOIN_CODE_LENGTH: usize = 10;
+
+mod add;
+pub use add::add;
+
+mod remove;
+pub use remove::remove;
+
+mod new_join_code;
+pub use new_join_code::new_join_code;
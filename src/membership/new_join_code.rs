@@ -0,0 +1,38 @@
+use super::JOIN_CODE_LENGTH;
+use crate::fault::Fault;
+use crate::models::JoinCode;
+use crate::util;
+use chrono::Utc;
+use warp::reject;
+
+/// A new join code for the office with the expiry and usage cap of `requested`.
+pub fn new_join_code(
+    office_id: &str,
+    requested: JoinCode,
+    created_by: &str,
+) -> Result<JoinCode, warp::Rejection> {
+    if requested
+        .expires
+        .is_some_and(|expires| expires <= Utc::now())
+    {
+        return Err(reject::custom(Fault::IllegalArgument(String::from(
+            "Join code would already have expired",
+        ))));
+    }
+    if requested.max_uses == Some(0) {
+        return Err(reject::custom(Fault::IllegalArgument(String::from(
+            "Join code must be usable at least once",
+        ))));
+    }
+
+    Ok(JoinCode {
+        id: util::random_string(JOIN_CODE_LENGTH),
+        office_id: office_id.to_string(),
+        expires: requested.expires,
+        max_uses: requested.max_uses,
+        uses: 0,
+        created_by: created_by.to_string(),
+        modified: Utc::now(),
+        created: Utc::now(),
+    })
+}
@@ -0,0 +1,21 @@
+use crate::models::User;
+use chrono::Utc;
+
+/// Removes the user from the office along with the user's roles for it, returns false if the user
+/// was not a member. Bumps the user's role generation if roles were removed.
+pub fn remove(user: &mut User, office_id: &str) -> bool {
+    if !user.office_ids.iter().any(|o| o == office_id) {
+        return false;
+    }
+    user.office_ids.retain(|o| o != office_id);
+    user.office_joined_at.remove(office_id);
+
+    let role_count = user.roles.len();
+    user.roles
+        .retain(|role| role.sub.as_deref() != Some(office_id));
+    if user.roles.len() != role_count {
+        user.role_generation += 1;
+    }
+    user.modified = Utc::now();
+    true
+}
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A privileged or administrative action, as recorded in the append-only audit log.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub id: String,
+
+    // Id of the user or service account performing the action.
+    pub actor: String,
+
+    // Id of the user the actor impersonated, if the action was performed with an impersonation
+    // token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub impersonated: Option<String>,
+
+    // Name of the policy action, e.g. `series.put`.
+    pub action: String,
+
+    // Id of the document the action was performed on, or the method and path of an impersonated
+    // request.
+    pub target: String,
+
+    // Offices the target belongs to, the entry shows in their audit logs.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub office_ids: Vec<String>,
+
+    // Top level fields of the target that changed, as they were before and after the action. A
+    // field missing on one side was absent.
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    #[serde(default)]
+    pub before: Map<String, Value>,
+
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    #[serde(default)]
+    pub after: Map<String, Value>,
+
+    // Id of the request performing the action, see `filters::with_request_id`.
+    pub request_id: String,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Query string filtering the audit log, e.g. `?actor=...&from=2021-06-01T00:00:00Z`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditQuery {
+    // Only the global audit log filters by office, an office's log is always filtered by it.
+    pub office_id: Option<String>,
+
+    pub actor: Option<String>,
+
+    // Inclusive.
+    pub from: Option<DateTime<Utc>>,
+
+    // Exclusive.
+    pub to: Option<DateTime<Utc>>,
+}
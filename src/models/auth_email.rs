@@ -1,3 +1,4 @@
+use crate::models::TwoFactor;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -8,4 +9,8 @@ pub struct AuthEmail {
     pub passhash: String,
 
     pub user_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub two_factor: Option<TwoFactor>,
 }
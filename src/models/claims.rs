@@ -1,4 +1,4 @@
-use crate::models::Role;
+use crate::models::{Role, TokenPurpose};
 use crate::util;
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -14,6 +14,44 @@ pub struct Claims {
     #[serde(skip_serializing_if = "util::is_empty")]
     #[serde(default)]
     pub rol: Vec<Role>,
+
+    // Token id, only set on refresh tokens.
+    #[serde(skip_serializing_if = "util::is_none")]
+    #[serde(default)]
+    pub jti: Option<String>,
+
+    // Refresh token family id, i.e. the session the token was issued for.
+    #[serde(skip_serializing_if = "util::is_none")]
+    #[serde(default)]
+    pub fam: Option<String>,
+
+    // Token generation of the user when the token was issued, see `User::token_generation`.
+    #[serde(default)]
+    pub gen: u32,
+
+    // Role generation of the user when the token was issued, see `User::role_generation`.
+    #[serde(default)]
+    pub rgn: u32,
+
+    // Only set on single purpose tokens, which are never accepted as access or refresh tokens.
+    #[serde(skip_serializing_if = "util::is_none")]
+    #[serde(default)]
+    pub pur: Option<TokenPurpose>,
+
+    // Email the single purpose token was issued for.
+    #[serde(skip_serializing_if = "util::is_none")]
+    #[serde(default)]
+    pub eml: Option<String>,
+
+    // Id of the admin acting as `sub`, only set on impersonation tokens.
+    #[serde(skip_serializing_if = "util::is_none")]
+    #[serde(default)]
+    pub act: Option<String>,
+
+    // Whether the impersonation token may be used for more than reading.
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub wrt: bool,
 }
 
 impl Claims {
@@ -32,6 +70,14 @@ impl Claims {
             sub,
             exp,
             rol: rol.to_vec(),
+            jti: None,
+            fam: None,
+            gen: 0,
+            rgn: 0,
+            pur: None,
+            eml: None,
+            act: None,
+            wrt: false,
         }
     }
 }
@@ -1,23 +1,14 @@
-use crate::models::{I18nString, RoleFlags};
+use crate::models::I18nString;
 use crate::util;
-use crate::EPISODE_COLLECTION;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use third_pact::model;
 
-#[model(
-    Collection(EPISODE_COLLECTION),
-    PUT(RoleFlags::OFFICE_CONTENT_ADMIN),
-    DELETE(RoleFlags::OFFICE_CONTENT_ADMIN)
-)]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Episode {
-    #[prim]
     #[serde(default)]
     pub id: String,
 
-    #[partition]
     pub office_id: String,
 
     pub series_id: String,
@@ -57,6 +48,11 @@ pub struct Episode {
     #[serde(default)]
     pub deleted: bool,
 
+    // Set along with `deleted` when the office was deleted, restoring the office restores it.
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub office_deleted: bool,
+
     #[serde(default = "Utc::now")]
     pub modified: DateTime<Utc>,
 
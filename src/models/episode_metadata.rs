@@ -1,4 +1,4 @@
-use crate::models::RoleFlags;
+use crate::policy;
 use crate::util;
 use crate::EPISODE_METADATA_COLLECTION;
 use chrono::{DateTime, Utc};
@@ -8,8 +8,8 @@ use third_pact::model;
 
 #[model(
     Collection(EPISODE_METADATA_COLLECTION),
-    GET(SELF, user_id),
-    DELETE(RoleFlags::OFFICE_CONTENT_ADMIN)
+    GET(policy::EPISODE_METADATA_GET, user_id),
+    DELETE(policy::EPISODE_METADATA_DELETE, user_id)
 )]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
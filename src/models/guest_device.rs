@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The guest user a device signs in as until the guest is upgraded to a full account.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GuestDevice {
+    // Hash of the device id, see `util::hash_token`.
+    pub id: String,
+
+    pub user_id: String,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
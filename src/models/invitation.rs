@@ -0,0 +1,40 @@
+use crate::models::Role;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An invitation for an email address to join an office. Accepting it adds the office to the
+/// accepting user and grants the roles. The invitation is deleted once accepted or revoked.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Invitation {
+    pub id: String,
+
+    pub office_id: String,
+
+    // Normalised to lowercase.
+    pub email: String,
+
+    // Office roles granted on acceptance, their subject is the office.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub roles: Vec<Role>,
+
+    // Hash of the secret part of the accept token, see `util::hash_token`. Cleared before the
+    // invitation is returned from the api.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default)]
+    pub secret_hash: String,
+
+    pub invited_by: String,
+
+    pub expires: DateTime<Utc>,
+
+    // When the invitation was last sent, resending replaces the accept token.
+    pub sent: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub modified: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
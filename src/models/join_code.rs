@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A code letting any signed in user join the office, e.g. shared as a link. Posting a join code
+/// only sets `expires` and `maxUses`, the rest is filled in.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JoinCode {
+    // The code itself.
+    #[serde(default)]
+    pub id: String,
+
+    #[serde(default)]
+    pub office_id: String,
+
+    // The code can not be redeemed after this, it never expires if missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub expires: Option<DateTime<Utc>>,
+
+    // How many times the code can be redeemed, unlimited if missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub max_uses: Option<u32>,
+
+    #[serde(default)]
+    pub uses: u32,
+
+    #[serde(default)]
+    pub created_by: String,
+
+    #[serde(default = "Utc::now")]
+    pub modified: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
@@ -0,0 +1,45 @@
+use crate::util;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// An account at an external identity provider that can be used to sign in as `user_id`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedIdentity {
+    // See `LinkedIdentity::id_for`.
+    pub id: String,
+
+    pub issuer: String,
+
+    pub subject: String,
+
+    pub user_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub email: Option<String>,
+
+    // Whether `email` is a relay address handed out by the provider, e.g. Apple's "Hide My Email".
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub private_email: bool,
+
+    #[serde(default = "Utc::now")]
+    pub modified: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
+
+impl LinkedIdentity {
+    /// Issuers are urls, which may not be used in document ids, so the id is a hash of the issuer
+    /// and the subject.
+    pub fn id_for(issuer: &str, subject: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(issuer.as_bytes());
+        hasher.update(b" ");
+        hasher.update(subject.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
@@ -1,5 +1,7 @@
 mod claims;
 pub use claims::Claims;
+mod token_purpose;
+pub use token_purpose::TokenPurpose;
 mod role;
 pub use role::Role;
 mod role_flags;
@@ -8,6 +10,8 @@ mod device;
 pub use device::Device;
 mod user;
 pub use user::User;
+mod guest_device;
+pub use guest_device::GuestDevice;
 mod auth_email;
 pub use auth_email::AuthEmail;
 mod i18n_string;
@@ -28,3 +32,35 @@ mod recommendation;
 pub use recommendation::Recommendation;
 mod category;
 pub use category::Category;
+mod refresh_token_family;
+pub use refresh_token_family::RefreshTokenFamily;
+mod linked_identity;
+pub use linked_identity::LinkedIdentity;
+mod password_reset;
+pub use password_reset::PasswordReset;
+mod signin_code;
+pub use signin_code::SigninCode;
+mod signin_attempts;
+pub use signin_attempts::SigninAttempts;
+mod two_factor;
+pub use two_factor::TwoFactor;
+mod service_account;
+pub use service_account::ServiceAccount;
+mod invitation;
+pub use invitation::Invitation;
+mod audit_entry;
+pub use audit_entry::AuditEntry;
+mod audit_query;
+pub use audit_query::AuditQuery;
+mod join_code;
+pub use join_code::JoinCode;
+mod office_settings;
+pub use office_settings::OfficeSettings;
+mod office_deletion;
+pub use office_deletion::OfficeDeletion;
+mod product;
+pub use product::Product;
+mod product_key;
+pub use product_key::ProductKey;
+mod office_content;
+pub use office_content::OfficeContent;
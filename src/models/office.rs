@@ -1,17 +1,11 @@
-use crate::models::{I18nString, RoleFlags};
+use crate::models::I18nString;
 use crate::util;
 use crate::OFFICE_COLLECTION;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use third_pact::model;
 
-#[model(
-    Collection(OFFICE_COLLECTION),
-    GET(),
-    POST(RoleFlags::GLOBAL_CONTENT_ADMIN),
-    PUT(RoleFlags::OFFICE_CONTENT_ADMIN, id),
-    DELETE(RoleFlags::OFFICE_CONTENT_ADMIN, id)
-)]
+#[model(Collection(OFFICE_COLLECTION), GET())]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Office {
@@ -26,6 +20,11 @@ pub struct Office {
     #[serde(default)]
     pub deleted: bool,
 
+    // Whether the office's admins must sign in with a second factor to use their admin roles.
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub require_two_factor: bool,
+
     #[serde(default = "Utc::now")]
     pub modified: DateTime<Utc>,
 }
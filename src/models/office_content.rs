@@ -0,0 +1,91 @@
+use crate::models::{Category, Episode, Recommendation, Series};
+use crate::policy::{self, Action};
+use crate::{CATEGORY_COLLECTION, EPISODE_COLLECTION, RECOMMENDED_COLLECTION, SERIES_COLLECTION};
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// A document of an office's content, posted, put and deleted by its content admins through
+/// `api::office_content_post`, `api::office_content_put` and `api::office_content_delete`.
+pub trait OfficeContent: Serialize + DeserializeOwned + Clone + Send + Sync + 'static {
+    // Collection of the documents, partitioned by office id.
+    const COLLECTION: &'static str;
+
+    const POST: Action;
+    const PUT: Action;
+    const DELETE: Action;
+
+    fn id(&self) -> &str;
+
+    fn set_id(&mut self, id: String);
+
+    fn office_id(&self) -> &str;
+
+    /// Sets the modification time and takes the fields only the server changes from the stored
+    /// document, if there is one.
+    fn prepare(&mut self, old: Option<&Self>);
+
+    fn mark_deleted(&mut self);
+}
+
+macro_rules! office_content {
+    ($model:ty, $collection:expr, $post:expr, $put:expr, $delete:expr) => {
+        impl OfficeContent for $model {
+            const COLLECTION: &'static str = $collection;
+
+            const POST: Action = $post;
+            const PUT: Action = $put;
+            const DELETE: Action = $delete;
+
+            fn id(&self) -> &str {
+                &self.id
+            }
+
+            fn set_id(&mut self, id: String) {
+                self.id = id;
+            }
+
+            fn office_id(&self) -> &str {
+                &self.office_id
+            }
+
+            fn prepare(&mut self, old: Option<&Self>) {
+                self.office_deleted = old.is_some_and(|old| old.office_deleted);
+                self.modified = Utc::now();
+            }
+
+            fn mark_deleted(&mut self) {
+                self.deleted = true;
+                self.modified = Utc::now();
+            }
+        }
+    };
+}
+
+office_content!(
+    Series,
+    SERIES_COLLECTION,
+    policy::SERIES_POST,
+    policy::SERIES_PUT,
+    policy::SERIES_DELETE
+);
+office_content!(
+    Episode,
+    EPISODE_COLLECTION,
+    policy::EPISODE_POST,
+    policy::EPISODE_PUT,
+    policy::EPISODE_DELETE
+);
+office_content!(
+    Category,
+    CATEGORY_COLLECTION,
+    policy::CATEGORY_POST,
+    policy::CATEGORY_PUT,
+    policy::CATEGORY_DELETE
+);
+office_content!(
+    Recommendation,
+    RECOMMENDED_COLLECTION,
+    policy::RECOMMENDATION_POST,
+    policy::RECOMMENDATION_PUT,
+    policy::RECOMMENDATION_DELETE
+);
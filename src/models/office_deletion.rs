@@ -0,0 +1,42 @@
+use crate::util;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Progress of deleting an office along with its content, subscriptions and memberships, or of
+/// restoring it. There is one per office, with the office's id, which a restore reuses.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OfficeDeletion {
+    #[serde(default)]
+    pub id: String,
+
+    // Whether the office is being restored rather than deleted.
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub restore: bool,
+
+    // The steps done so far, see `office_deletion::STEPS`.
+    #[serde(default)]
+    pub completed_steps: Vec<String>,
+
+    pub total_steps: usize,
+
+    // How many documents have been marked so far.
+    #[serde(default)]
+    pub processed: usize,
+
+    pub requested_by: String,
+
+    // The office can not be restored after this.
+    pub restorable_until: DateTime<Utc>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub finished: Option<DateTime<Utc>>,
+
+    #[serde(default = "Utc::now")]
+    pub modified: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How an office presents itself in the apps, one per office with the office's id. The images are
+/// uploaded separately and kept when the settings are put.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OfficeSettings {
+    #[serde(default)]
+    pub id: String,
+
+    #[serde(default)]
+    pub office_id: String,
+
+    // BCP 47 language tag, e.g. `sv-SE`, one of the supported languages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub default_language: Option<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub supported_languages: Vec<String>,
+
+    // Blob ids of the uploaded images.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub logo_image: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub hero_image: Option<String>,
+
+    // Colours as `#rrggbb`, most prominent first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub accent_colours: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub support_email: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub terms_url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub privacy_policy_url: Option<String>,
+
+    // Store product ids of the subscriptions to the office, for the apps to offer. Never stored,
+    // the polls take them from the product catalog.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(skip_deserializing)]
+    pub product_ids: Vec<String>,
+
+    #[serde(default = "Utc::now")]
+    pub modified: DateTime<Utc>,
+}
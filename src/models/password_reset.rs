@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A pending password reset. The reset token itself is only ever sent to the user, the document
+/// is stored under its hash.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordReset {
+    // Hash of the reset token, see `util::hash_token`.
+    pub id: String,
+
+    // Id of the auth email whose password is reset.
+    pub email: String,
+
+    pub user_id: String,
+
+    pub expires: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
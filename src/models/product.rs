@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use in_app_purchases::{Platform, ProductType};
+use serde::{Deserialize, Serialize};
+
+/// A store product in the catalog, purchasing it entitles to the office. Purchases of products
+/// missing from the catalog are refused.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Product {
+    #[serde(default)]
+    pub id: String,
+
+    #[serde(default)]
+    pub office_id: String,
+
+    pub platform: Platform,
+
+    // The product id in App Store Connect or Google Play.
+    pub product_id: String,
+
+    // The app's bundle id on Apple, its package name on Google.
+    pub package_name: String,
+
+    pub product_type: ProductType,
+
+    // How many days a purchase entitles to the office, for as long as the store renews it if
+    // missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub entitlement_days: Option<i64>,
+
+    #[serde(default = "Utc::now")]
+    pub modified: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Reserves a store product for a single product of the catalog. Inserting a second key for the
+/// same store product fails, which keeps a store product from entitling to two offices.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductKey {
+    // Hash of the store product's platform, package name and product id, see `product_catalog::key`.
+    pub id: String,
+
+    pub office_id: String,
+
+    // Id of the product in the catalog.
+    pub catalog_id: String,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
@@ -0,0 +1,49 @@
+use crate::models::Device;
+use crate::util;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A chain of refresh tokens stemming from a single signin, i.e. a session. Only the refresh token
+/// with id `current` may be exchanged, presenting any earlier token of the family revokes it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshTokenFamily {
+    pub id: String,
+
+    pub user_id: String,
+
+    // Id (jti) of the only refresh token in the family that is still valid.
+    pub current: String,
+
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub revoked: bool,
+
+    // Whether the signin that started the family was completed with a second factor.
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub two_factor: bool,
+
+    // Device registered for push notifications from the session, see `user_device_post`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub device: Option<Device>,
+
+    // Client ip of the signin, updated on every refresh.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub ip: Option<String>,
+
+    // When the session was last refreshed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub refreshed: Option<DateTime<Utc>>,
+
+    pub expires: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub modified: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
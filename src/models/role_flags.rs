@@ -11,5 +11,6 @@ bitflags! {
         const GLOBAL_CONTENT_ADMIN   = 0b0000010000;
         const GLOBAL_BILLING_ADMIN   = 0b0000100000;
         const GLOBAL_PERSONNEL_ADMIN = 0b0001000000;
+        const SCHEDULED_JOBS         = 0b0010000000;
     }
 }
@@ -1,17 +1,11 @@
-use crate::models::{I18nString, RoleFlags};
+use crate::models::I18nString;
 use crate::util;
 use crate::SERIES_COLLECTION;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use third_pact::model;
 
-#[model(
-    Collection(SERIES_COLLECTION),
-    GET(),
-    POST(RoleFlags::OFFICE_CONTENT_ADMIN, office_id),
-    PUT(RoleFlags::OFFICE_CONTENT_ADMIN, office_id),
-    DELETE(RoleFlags::OFFICE_CONTENT_ADMIN, office_id)
-)]
+#[model(Collection(SERIES_COLLECTION), GET())]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Series {
@@ -40,6 +34,11 @@ pub struct Series {
     #[serde(default)]
     pub deleted: bool,
 
+    // Set along with `deleted` when the office was deleted, restoring the office restores it.
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub office_deleted: bool,
+
     #[serde(default = "Utc::now")]
     pub modified: DateTime<Utc>,
 
@@ -1,4 +1,4 @@
-use crate::models::RoleFlags;
+use crate::policy;
 use crate::util;
 use crate::SERIES_USER_DATA_COLLECTION;
 use chrono::{DateTime, Utc};
@@ -7,9 +7,9 @@ use third_pact::model;
 
 #[model(
     Collection(SERIES_USER_DATA_COLLECTION),
-    GET(SELF, user_id),
-    PUT(SELF, user_id),
-    DELETE(RoleFlags::OFFICE_CONTENT_ADMIN)
+    GET(policy::SERIES_USER_DATA_GET, user_id),
+    PUT(policy::SERIES_USER_DATA_PUT, user_id),
+    DELETE(policy::SERIES_USER_DATA_DELETE, user_id)
 )]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,46 @@
+use crate::models::Role;
+use crate::util;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A machine client, e.g. a scheduled job, that authenticates with an API key instead of signing
+/// in as a user.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccount {
+    pub id: String,
+
+    pub name: String,
+
+    // The roles requests made with the key are given, offices are scoped through `Role::sub`.
+    #[serde(skip_serializing_if = "util::is_empty")]
+    #[serde(default)]
+    pub roles: Vec<Role>,
+
+    // Hash of the secret part of the API key, see `util::hash_token`. Cleared before the account
+    // is returned from the api.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default)]
+    pub key_hash: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub expires: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub last_used: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub revoked: bool,
+
+    // Id of the user that created the account.
+    pub created_by: String,
+
+    #[serde(default = "Utc::now")]
+    pub modified: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Failed signin attempts for one email or client ip, see `throttle::Subject`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SigninAttempts {
+    // Hash of `key`, see `util::hash_token`.
+    pub id: String,
+
+    // What is throttled, e.g. "email jane@example.com" or "ip 192.0.2.1".
+    pub key: String,
+
+    // Failures since the last lockout or successful signin.
+    #[serde(default)]
+    pub failures: u32,
+
+    // Lockouts in a row, every lockout is twice as long as the previous one.
+    #[serde(default)]
+    pub lockouts: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub locked_until: Option<DateTime<Utc>>,
+
+    #[serde(default = "Utc::now")]
+    pub modified: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
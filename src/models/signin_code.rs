@@ -0,0 +1,42 @@
+use crate::util;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A pending passwordless signin. The link token and the code are only ever sent to the user, the
+/// document holds their hashes. There is at most one per email, a new request replaces it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SigninCode {
+    // Hash of the email, see `util::hash_token`.
+    pub id: String,
+
+    // Id of the auth email that signs in.
+    pub email: String,
+
+    pub user_id: String,
+
+    // Hash of the token in the emailed link.
+    pub link_hash: String,
+
+    // Hash of the code together with the email, since a six digit code alone is easily reversed.
+    pub code_hash: String,
+
+    // Wrong codes entered so far.
+    #[serde(default)]
+    pub attempts: u32,
+
+    pub expires: DateTime<Utc>,
+
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
+
+impl SigninCode {
+    pub fn id_for(email: &str) -> String {
+        util::hash_token(email)
+    }
+
+    pub fn hash_code(email: &str, code: &str) -> String {
+        util::hash_token(&format!("{} {}", email, code))
+    }
+}
@@ -54,6 +54,11 @@ pub struct Subscription {
     #[serde(default)]
     pub deleted: bool,
 
+    // Set along with `deleted` when the office was deleted, restoring the office restores it.
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub office_deleted: bool,
+
     // Denormalized for db queries.
     pub office_id: String,
 
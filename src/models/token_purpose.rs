@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// What a single purpose token, e.g. a link sent by email, may be used for. Access and refresh
+/// tokens have no purpose.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenPurpose {
+    VerifyEmail,
+    // Completing a signin with the second factor.
+    TwoFactor,
+    // Confirming a change of email from the new address.
+    ChangeEmail,
+}
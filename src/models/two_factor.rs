@@ -0,0 +1,24 @@
+use crate::util;
+use serde::{Deserialize, Serialize};
+
+/// TOTP second factor of an auth email.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactor {
+    // The TOTP secret, encrypted with the two factor encryption key.
+    pub secret: String,
+
+    // Unset until the user has shown that an authenticator has been set up with the secret.
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub confirmed: bool,
+
+    // Hashes of the unused recovery codes, see `util::hash_token`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub recovery_codes: Vec<String>,
+
+    // Time step of the last accepted code, so that a code can only be used once.
+    #[serde(default)]
+    pub last_step: u64,
+}
@@ -1,6 +1,7 @@
 use crate::{models::Device, util, Role};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -16,6 +17,16 @@ pub struct User {
     #[serde(default)]
     pub test: bool,
 
+    // Anonymous user created from a device, until upgraded through signup or a provider signin.
+    #[serde(skip_serializing_if = "util::is_false")]
+    #[serde(default)]
+    pub guest: bool,
+
+    // Hash of the device id a guest was created from, see `GuestDevice`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub guest_device: Option<String>,
+
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[serde(default)]
     pub roles: Vec<Role>,
@@ -43,6 +54,18 @@ pub struct User {
     #[serde(default)]
     pub office_ids: Vec<String>,
 
+    // When the user joined each of the offices, so that the poll sends the content of an office
+    // joined since the last poll. Offices joined before this was recorded are missing.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    #[serde(default)]
+    pub office_joined_at: HashMap<String, DateTime<Utc>>,
+
+    // Offices the user was a member of when they were deleted, moved back to `office_ids` if the
+    // office is restored.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub deleted_office_ids: Vec<String>,
+
     #[serde(skip_serializing_if = "Vec::is_empty")]
     #[serde(default)]
     pub saved_series: Vec<String>,
@@ -58,6 +81,30 @@ pub struct User {
     #[serde(default)]
     pub favourite_episode_ids: Vec<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub email_verified_at: Option<DateTime<Utc>>,
+
+    // Address the user has asked to change their email to, until the change is confirmed from it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub pending_email: Option<String>,
+
+    // When the last verification email was sent, used to throttle resends.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub email_verification_sent_at: Option<DateTime<Utc>>,
+
+    // Bumped whenever every token issued to the user should stop working, e.g. on sign out
+    // everywhere or a password change.
+    #[serde(default)]
+    pub token_generation: u32,
+
+    // Bumped whenever the user's roles change. Access tokens of an earlier role generation are
+    // rejected, so that the client refreshes them and gets the current roles.
+    #[serde(default)]
+    pub role_generation: u32,
+
     #[serde(default = "Utc::now")]
     pub modified: DateTime<Utc>,
 
@@ -0,0 +1,37 @@
+use super::{NOT_DELETED, OFFICE_DELETED};
+use chrono::Utc;
+use cosmos_utils::{query_crosspartition_etag, upsert, CosmosErrorStruct};
+use serde_json::{json, Value};
+
+/// Marks the office's documents in a collection partitioned by office id as deleted along with the
+/// office, or unmarks them, returns the number of marked documents. The documents are handled as
+/// json so that any such collection can be marked.
+pub async fn mark_documents(
+    collection: &str,
+    office_id: &str,
+    delete: bool,
+) -> Result<usize, CosmosErrorStruct> {
+    let q = format!(
+        "SELECT * FROM {} o WHERE {}",
+        collection,
+        if delete { NOT_DELETED } else { OFFICE_DELETED }
+    );
+    let documents: Vec<(Value, String)> =
+        query_crosspartition_etag(collection, [&office_id], q, -1, false).await?;
+
+    let count = documents.len();
+    for (mut document, etag) in documents {
+        if let Some(fields) = document.as_object_mut() {
+            if delete {
+                fields.insert(String::from("deleted"), json!(true));
+                fields.insert(String::from("officeDeleted"), json!(true));
+            } else {
+                fields.remove("deleted");
+                fields.remove("officeDeleted");
+            }
+            fields.insert(String::from("modified"), json!(Utc::now()));
+        }
+        upsert(collection, [&office_id], &document, Some(&etag)).await?;
+    }
+    Ok(count)
+}
@@ -0,0 +1,38 @@
+use crate::models::User;
+use crate::USER_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::{query_crosspartition_etag, upsert, CosmosErrorStruct};
+
+/// Moves the office from the members' `office_ids` to their `deleted_office_ids`, or back, returns
+/// the number of moved members. The members keep their roles for the office, for a restore, and
+/// `user_poll` keeps sending them the deletion of the office and its content.
+pub async fn mark_members(office_id: &str, delete: bool) -> Result<usize, CosmosErrorStruct> {
+    let q = format!(
+        "SELECT * FROM {} u WHERE ARRAY_CONTAINS(u.{}, \"{}\")",
+        USER_COLLECTION,
+        if delete {
+            "officeIds"
+        } else {
+            "deletedOfficeIds"
+        },
+        office_id
+    );
+    let users: Vec<(User, String)> =
+        query_crosspartition_etag(USER_COLLECTION, [&()], q, -1, true).await?;
+
+    let count = users.len();
+    for (mut user, etag) in users {
+        let (from, to) = if delete {
+            (&mut user.office_ids, &mut user.deleted_office_ids)
+        } else {
+            (&mut user.deleted_office_ids, &mut user.office_ids)
+        };
+        from.retain(|o| o != office_id);
+        if !to.iter().any(|o| o == office_id) {
+            to.push(office_id.to_string());
+        }
+        user.modified = Utc::now();
+        upsert(USER_COLLECTION, [&user.id], &user, Some(&etag)).await?;
+    }
+    Ok(count)
+}
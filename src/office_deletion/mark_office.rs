@@ -0,0 +1,13 @@
+use crate::models::Office;
+use crate::OFFICE_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::{get, upsert, CosmosErrorStruct};
+
+/// Marks the office itself as deleted, or not, returns the number of marked documents.
+pub async fn mark_office(office_id: &str, delete: bool) -> Result<usize, CosmosErrorStruct> {
+    let (mut office, etag): (Office, _) = get(OFFICE_COLLECTION, [&office_id], &office_id).await?;
+    office.deleted = delete;
+    office.modified = Utc::now();
+    upsert(OFFICE_COLLECTION, [&office_id], &office, Some(&etag)).await?;
+    Ok(1)
+}
@@ -0,0 +1,43 @@
+use super::{NOT_DELETED, OFFICE_DELETED};
+use crate::models::Subscription;
+use crate::SUBSCRIPTION_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::{query_crosspartition_etag, upsert, CosmosErrorStruct};
+
+/// Marks the active subscriptions to the office as deleted along with the office, or unmarks
+/// them, returns the number of marked subscriptions.
+pub async fn mark_subscriptions(office_id: &str, delete: bool) -> Result<usize, CosmosErrorStruct> {
+    let now = Utc::now();
+    let q = if delete {
+        format!(
+            "SELECT * FROM {} o WHERE o.officeId = \"{}\" AND {} AND (NOT IS_DEFINED(o['end']) \
+            OR o['end'] >= \"{}\")",
+            SUBSCRIPTION_COLLECTION,
+            office_id,
+            NOT_DELETED,
+            now.to_rfc3339()
+        )
+    } else {
+        format!(
+            "SELECT * FROM {} o WHERE o.officeId = \"{}\" AND {}",
+            SUBSCRIPTION_COLLECTION, office_id, OFFICE_DELETED
+        )
+    };
+    let subscriptions: Vec<(Subscription, String)> =
+        query_crosspartition_etag(SUBSCRIPTION_COLLECTION, [&()], q, -1, true).await?;
+
+    let count = subscriptions.len();
+    for (mut subscription, etag) in subscriptions {
+        subscription.deleted = delete;
+        subscription.office_deleted = delete;
+        subscription.modified = now;
+        upsert(
+            SUBSCRIPTION_COLLECTION,
+            [&subscription.user_id],
+            &subscription,
+            Some(&etag),
+        )
+        .await?;
+    }
+    Ok(count)
+}
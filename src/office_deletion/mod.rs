@@ -0,0 +1,42 @@
+//! Deleting an office along with everything that belongs to it, and restoring it. The documents
+//! are only marked, as `deleted` and `officeDeleted`, so that a restore can tell them from those
+//! deleted on their own. The work is done in steps by a background job whose progress is kept in
+//! an `OfficeDeletion`, a job that stops half way is resumed by the cron.
+
+/// The steps of a deletion in order, a restore runs them in reverse.
+pub const STEPS: &[&str] = &[
+    "office",
+    "series",
+    "episodes",
+    "categories",
+    "recommendations",
+    "subscriptions",
+    "members",
+];
+
+/// How long a deleted office can be restored.
+pub const RETENTION_DAYS: i64 = 30;
+
+/// A job that has made no progress for this long is considered stopped and is resumed.
+pub const STALLED_AFTER_MINUTES: i64 = 10;
+
+/// Condition on `o` for the documents that a deletion marks.
+const NOT_DELETED: &str = "(NOT IS_DEFINED(o.deleted) OR o.deleted = false)";
+
+/// Condition on `o` for the documents that a restore unmarks.
+const OFFICE_DELETED: &str = "o.officeDeleted = true";
+
+mod run;
+pub use run::run;
+
+mod mark_office;
+use mark_office::mark_office;
+
+mod mark_documents;
+use mark_documents::mark_documents;
+
+mod mark_subscriptions;
+use mark_subscriptions::mark_subscriptions;
+
+mod mark_members;
+use mark_members::mark_members;
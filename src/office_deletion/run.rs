@@ -0,0 +1,108 @@
+use super::{mark_documents, mark_members, mark_office, mark_subscriptions, STEPS};
+use crate::models::OfficeDeletion;
+use crate::util::log_critical;
+use crate::{
+    CATEGORY_COLLECTION, EPISODE_COLLECTION, OFFICE_DELETION_COLLECTION, RECOMMENDED_COLLECTION,
+    SERIES_COLLECTION,
+};
+use chrono::Utc;
+use cosmos_utils::{get, upsert, CosmosErrorStruct};
+
+/// Runs the steps of the office's deletion or restore that are not done yet, saving the progress
+/// after each. Errors are only logged, the job is then resumed by the cron.
+pub async fn run(office_id: String) {
+    if let Err(e) = run_steps(&office_id).await {
+        log_critical(format!(
+            "Deletion or restore of office {} stopped due to {}",
+            office_id, e
+        ));
+    }
+}
+
+async fn run_steps(office_id: &str) -> Result<(), CosmosErrorStruct> {
+    let (mut job, mut etag): (OfficeDeletion, _) =
+        get(OFFICE_DELETION_COLLECTION, [&office_id], &office_id).await?;
+    if job.finished.is_some() {
+        return Ok(());
+    }
+    let delete = !job.restore;
+
+    for step in pending_steps(job.restore, &job.completed_steps) {
+        job.processed += match step {
+            "office" => mark_office(office_id, delete).await?,
+            "series" => mark_documents(SERIES_COLLECTION, office_id, delete).await?,
+            "episodes" => mark_documents(EPISODE_COLLECTION, office_id, delete).await?,
+            "categories" => mark_documents(CATEGORY_COLLECTION, office_id, delete).await?,
+            "recommendations" => mark_documents(RECOMMENDED_COLLECTION, office_id, delete).await?,
+            "subscriptions" => mark_subscriptions(office_id, delete).await?,
+            "members" => mark_members(office_id, delete).await?,
+            _ => 0,
+        };
+        job.completed_steps.push(step.to_string());
+        job.modified = Utc::now();
+        // Fails if another run of the job got ahead of this one, which then stops.
+        etag = upsert(OFFICE_DELETION_COLLECTION, [&office_id], &job, Some(&etag)).await?;
+    }
+
+    job.finished = Some(Utc::now());
+    job.modified = Utc::now();
+    upsert(OFFICE_DELETION_COLLECTION, [&office_id], &job, Some(&etag)).await?;
+    Ok(())
+}
+
+/// The steps of a deletion, or a restore, that are not completed yet in the order they are run.
+fn pending_steps(restore: bool, completed_steps: &[String]) -> Vec<&'static str> {
+    let steps: Vec<&'static str> = if restore {
+        STEPS.iter().rev().copied().collect()
+    } else {
+        STEPS.to_vec()
+    };
+    steps
+        .into_iter()
+        .filter(|step| !completed_steps.iter().any(|s| s == step))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pending_steps;
+
+    fn completed(steps: &[&str]) -> Vec<String> {
+        steps.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn deletion_marks_the_office_first_and_moves_the_members_last() {
+        let steps = pending_steps(false, &[]);
+        assert_eq!(steps.first(), Some(&"office"));
+        assert_eq!(steps.last(), Some(&"members"));
+        assert_eq!(steps.len(), super::STEPS.len());
+    }
+
+    #[test]
+    fn restore_moves_the_members_first_and_unmarks_the_office_last() {
+        let steps = pending_steps(true, &[]);
+        assert_eq!(steps.first(), Some(&"members"));
+        assert_eq!(steps.last(), Some(&"office"));
+        assert_eq!(steps.len(), super::STEPS.len());
+    }
+
+    #[test]
+    fn resumed_job_skips_completed_steps() {
+        assert_eq!(
+            pending_steps(false, &completed(&["office", "series", "episodes"])),
+            vec!["categories", "recommendations", "subscriptions", "members"]
+        );
+        assert_eq!(
+            pending_steps(true, &completed(&["members", "subscriptions"])),
+            vec![
+                "recommendations",
+                "categories",
+                "episodes",
+                "series",
+                "office"
+            ]
+        );
+        assert!(pending_steps(false, &completed(super::STEPS)).is_empty());
+    }
+}
@@ -0,0 +1,35 @@
+use crate::BREACHED_PASSWORDS_FILE;
+use lazy_static::lazy_static;
+use std::collections::HashSet;
+
+lazy_static! {
+    /// SHA-1 hashes of known breached passwords, read from a file in the format of the Pwned
+    /// Passwords list: one upper or lower case hex hash per line, optionally followed by `:count`.
+    /// The full list does not fit in memory, a list of the most common passwords is expected.
+    pub static ref BREACHED_PASSWORDS: HashSet<[u8; 20]> = match &*BREACHED_PASSWORDS_FILE {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)
+                .unwrap_or_else(|e| panic!("Could not read {}: {}", path, e));
+            text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| {
+                    let hex = line.split(':').next().unwrap_or_default().trim();
+                    parse_sha1(hex)
+                        .unwrap_or_else(|| panic!("Malformed breached password hash {}", hex))
+                })
+                .collect()
+        }
+        None => HashSet::new(),
+    };
+}
+
+fn parse_sha1(hex: &str) -> Option<[u8; 20]> {
+    if hex.len() != 40 || !hex.is_ascii() {
+        return None;
+    }
+    let mut hash = [0u8; 20];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(hash)
+}
@@ -0,0 +1,69 @@
+use super::{BREACHED_PASSWORDS, POLICY};
+use crate::fault::Fault;
+use crate::models::User;
+use sha1::{Digest, Sha1};
+use warp::reject;
+
+/// Checks a new password for the user against the password policy and the breached password
+/// list. Every violation is returned as its own `Fault::IllegalArgument` in a `Fault::Set`.
+pub fn check(password: &str, user: &User) -> Result<(), warp::Rejection> {
+    let mut faults = vec![];
+    let length = password.chars().count();
+    if length < POLICY.min_length {
+        faults.push(Fault::IllegalArgument(format!(
+            "Password must be at least {} characters long.",
+            POLICY.min_length
+        )));
+    }
+    if length > POLICY.max_length {
+        faults.push(Fault::IllegalArgument(format!(
+            "Password must be at most {} characters long.",
+            POLICY.max_length
+        )));
+    }
+
+    let lowercase = password.trim().to_lowercase();
+    for pattern in &POLICY.banned_patterns {
+        if lowercase.contains(&pattern.to_lowercase()) {
+            faults.push(Fault::IllegalArgument(format!(
+                "Password may not contain \"{}\".",
+                pattern
+            )));
+        }
+    }
+
+    let email = user.email.to_lowercase();
+    let local_part = email.split('@').next().unwrap_or_default();
+    if lowercase == email || lowercase == local_part {
+        faults.push(Fault::IllegalArgument(String::from(
+            "Password may not be the same as the email.",
+        )));
+    }
+    let names = [
+        Some(&user.first_name),
+        Some(&user.last_name),
+        user.preferred_name.as_ref(),
+    ];
+    if names
+        .iter()
+        .flatten()
+        .any(|name| !name.is_empty() && lowercase == name.trim().to_lowercase())
+    {
+        faults.push(Fault::IllegalArgument(String::from(
+            "Password may not be the same as the name.",
+        )));
+    }
+
+    let hash: [u8; 20] = Sha1::digest(password.as_bytes()).into();
+    if BREACHED_PASSWORDS.contains(&hash) {
+        faults.push(Fault::IllegalArgument(String::from(
+            "Password has appeared in a data breach.",
+        )));
+    }
+
+    if faults.is_empty() {
+        Ok(())
+    } else {
+        Err(reject::custom(Fault::Set(faults)))
+    }
+}
@@ -0,0 +1,77 @@
+use super::HASH_PARAMS;
+use crate::fault::Fault;
+use crate::util::log_critical;
+use lazy_static::lazy_static;
+use rand::Rng;
+use warp::reject;
+
+lazy_static! {
+    // Verified against in place of a missing hash, so that a user without a password takes as
+    // long to refuse as one with a wrong password.
+    static ref DUMMY_HASH: String = hash("dummy password").expect("Could not hash dummy password");
+}
+
+/// Hashes a password with the configured Argon2id parameters and a random salt.
+pub fn hash(password: &str) -> Result<String, warp::Rejection> {
+    let salt = rand::thread_rng().gen::<[u8; 32]>();
+    argon2::hash_encoded(password.as_bytes(), &salt, &HASH_PARAMS.config()).map_err(|e| {
+        reject::custom(Fault::Unspecified(format!(
+            "Could not hash password due to {}",
+            e
+        )))
+    })
+}
+
+/// Verifies a password against an encoded hash, whatever parameters it was made with. An empty
+/// hash, i.e. a user without a password, never verifies.
+pub fn verify(hash: &str, password: &str) -> Result<bool, warp::Rejection> {
+    if hash.is_empty() {
+        verify_encoded(&DUMMY_HASH, password)?;
+        return Ok(false);
+    }
+    verify_encoded(hash, password)
+}
+
+fn verify_encoded(hash: &str, password: &str) -> Result<bool, warp::Rejection> {
+    argon2::verify_encoded(hash, password.as_bytes()).map_err(|e| {
+        log_critical(format!("Could not verify password hash due to {}", e));
+        reject::custom(Fault::Unspecified(String::from(
+            "Could not verify password",
+        )))
+    })
+}
+
+/// Whether the hash was made with other parameters than the configured ones.
+pub fn needs_rehash(hash: &str) -> bool {
+    !hash.is_empty() && !hash.starts_with(&HASH_PARAMS.prefix())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash, needs_rehash, verify};
+    use crate::password::HASH_PARAMS;
+
+    #[test]
+    fn hashes_verify_and_are_current() {
+        let hash = hash("correct horse").unwrap();
+        assert!(hash.starts_with(&HASH_PARAMS.prefix()));
+        assert!(verify(&hash, "correct horse").unwrap());
+        assert!(!verify(&hash, "wrong horse").unwrap());
+        assert!(!needs_rehash(&hash));
+    }
+
+    #[test]
+    fn empty_hash_never_verifies() {
+        assert!(!verify("", "").unwrap());
+        assert!(!verify("", "dummy password").unwrap());
+        assert!(!needs_rehash(""));
+    }
+
+    #[test]
+    fn legacy_hashes_need_rehash() {
+        let legacy =
+            argon2::hash_encoded(b"password", b"somesaltsomesalt", &Default::default()).unwrap();
+        assert!(verify(&legacy, "password").unwrap());
+        assert!(needs_rehash(&legacy));
+    }
+}
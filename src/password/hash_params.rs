@@ -0,0 +1,59 @@
+use crate::PASSWORD_HASH_PARAMS;
+use argon2::{Config, ThreadMode, Variant, Version};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+
+/// Argon2id cost parameters for new password hashes, configured as JSON. Omitted fields take
+/// their default. Raising them makes existing hashes legacy, those are rehashed on signin.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct HashParams {
+    // Memory in KiB.
+    pub mem_cost: u32,
+
+    pub time_cost: u32,
+
+    pub lanes: u32,
+}
+
+impl Default for HashParams {
+    fn default() -> Self {
+        Self {
+            mem_cost: 19456,
+            time_cost: 2,
+            lanes: 1,
+        }
+    }
+}
+
+impl HashParams {
+    pub fn config(&self) -> Config<'static> {
+        Config {
+            variant: Variant::Argon2id,
+            version: Version::Version13,
+            mem_cost: self.mem_cost,
+            time_cost: self.time_cost,
+            lanes: self.lanes,
+            thread_mode: ThreadMode::Sequential,
+            ..Config::default()
+        }
+    }
+
+    /// The start of every encoded hash made with these parameters, up to the salt.
+    pub fn prefix(&self) -> String {
+        format!(
+            "$argon2id$v=19$m={},t={},p={}$",
+            self.mem_cost, self.time_cost, self.lanes
+        )
+    }
+}
+
+lazy_static! {
+    pub static ref HASH_PARAMS: HashParams = match &*PASSWORD_HASH_PARAMS {
+        Some(json) => {
+            serde_json::from_str(json).expect("PASSWORD_HASH_PARAMS are not valid parameters")
+        }
+        None => HashParams::default(),
+    };
+}
@@ -0,0 +1,14 @@
+mod policy;
+pub use policy::POLICY;
+
+mod breached;
+pub use breached::BREACHED_PASSWORDS;
+
+mod check;
+pub use check::check;
+
+mod hash_params;
+pub use hash_params::HASH_PARAMS;
+
+mod hash;
+pub use hash::{hash, needs_rehash, verify};
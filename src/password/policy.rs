@@ -0,0 +1,38 @@
+use crate::PASSWORD_POLICY;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+
+/// Requirements on new passwords, configured as JSON. Omitted fields take their default.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+
+    pub max_length: usize,
+
+    // Case insensitive substrings a password may not contain.
+    pub banned_patterns: Vec<String>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_length: 256,
+            banned_patterns: vec![
+                String::from("password"),
+                String::from("123456"),
+                String::from("qwerty"),
+                String::from("primecrime"),
+            ],
+        }
+    }
+}
+
+lazy_static! {
+    pub static ref POLICY: PasswordPolicy = match &*PASSWORD_POLICY {
+        Some(json) => serde_json::from_str(json).expect("PASSWORD_POLICY is not a valid policy"),
+        None => PasswordPolicy::default(),
+    };
+}
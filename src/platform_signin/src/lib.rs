@@ -1,8 +1,14 @@
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
-use serde::{Deserialize, Serialize};
-use time::OffsetDateTime;
+use chrono::Utc;
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+mod oidc;
+pub use oidc::{OidcClient, OidcIdToken};
+
+#[derive(Serialize_repr, Deserialize_repr, Clone, Debug)]
 #[repr(i32)]
 pub enum RealUserStatus {
     Unsupported = 0,
@@ -31,97 +37,161 @@ pub struct ValidTokenResponse {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct IdToken {
     pub iss: String,
-    pub sub: i64,
+    pub sub: String,
     pub aud: String,
-    pub iat: String,
-    pub exp: String,
-    pub nonce: String,
-    pub nonce_supported: bool,
-    pub email: String,
+    pub iat: i64,
+    pub exp: i64,
+    #[serde(default)]
+    pub nonce: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    // Apple sends the booleans either as JSON booleans or as the strings "true" and "false".
+    #[serde(default, deserialize_with = "bool_or_string")]
     pub email_verified: bool,
+    #[serde(default, deserialize_with = "bool_or_string")]
     pub is_private_email: bool,
-    pub real_user_status: RealUserStatus,
-    pub transfer_sub: String,
+    #[serde(default)]
+    pub real_user_status: Option<RealUserStatus>,
 }
 
-impl Client {
-    pub async fn authenticate(&self, code: String) -> Result<IdToken, PlatformSigninError> {
-        let grant_type = "authorization_code";
-        let body = format!(
-            "client_id:{}\nclient_secret:{}\ncode:{}\ngrant_type:{}",
-            self.client_id, self.client_secret, code, grant_type
-        );
-            dbg!(&body);
-        let resp = self
-            .client
-            .post("https://appleid.apple.com/auth/token")
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(body)
-            .send()
-            .await
-            .map_err(|_| PlatformSigninError::ReqwestError)?;
-        let token: AuthTokenResponse = if resp.status() == 200 {
-            let text = resp
-                .text()
-                .await
-                .map_err(|_| PlatformSigninError::ReqwestError)?;
-            dbg!(&text);
-            serde_json::from_str(&text).map_err(|_| PlatformSigninError::Serialize)?
+impl IdToken {
+    /// The email if Apple has verified it.
+    pub fn verified_email(&self) -> Option<&str> {
+        if self.email_verified {
+            self.email.as_deref()
         } else {
-            return Err(PlatformSigninError::BadRequest);
-        };
-        let validation = Validation {
-            algorithms: vec![Algorithm::ES256],
-            ..Default::default()
-        };
-        let token_data = decode::<IdToken>(
-            &token.id_token,
-            &DecodingKey::from_base64_secret(&code)
-                .map_err(|_| PlatformSigninError::SecretError)?,
-            &validation,
-        )
-        .map_err(|_| PlatformSigninError::ValidationError)?;
-        let id_token = token_data.claims;
+            None
+        }
+    }
+}
+
+pub(crate) fn bool_or_string<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrString {
+        Bool(bool),
+        String(String),
+    }
+    Ok(match BoolOrString::deserialize(deserializer)? {
+        BoolOrString::Bool(b) => b,
+        BoolOrString::String(s) => s == "true",
+    })
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct Jwk {
+    kid: String,
+    n: String,
+    e: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+/// Fetches the RSA key with id `kid` from the JSON Web Key Set at `jwks_uri`.
+pub(crate) async fn fetch_key(
+    client: &reqwest::Client,
+    jwks_uri: &str,
+    kid: &str,
+) -> Result<DecodingKey<'static>, PlatformSigninError> {
+    let resp = client
+        .get(jwks_uri)
+        .send()
+        .await
+        .map_err(|_| PlatformSigninError::ReqwestError)?;
+    if resp.status() != 200 {
+        return Err(PlatformSigninError::BadRequest);
+    }
+    let text = resp
+        .text()
+        .await
+        .map_err(|_| PlatformSigninError::ReqwestError)?;
+    let keys: JwkSet = serde_json::from_str(&text).map_err(|_| PlatformSigninError::Serialize)?;
+    let jwk = keys
+        .keys
+        .into_iter()
+        .find(|k| k.kid == kid)
+        .ok_or(PlatformSigninError::SecretError)?;
+    Ok(DecodingKey::from_rsa_components(&jwk.n, &jwk.e).into_static())
+}
+
+impl Client {
+    /// Exchanges an authorization code from the app for the user's id token. If the app passed a
+    /// nonce to Apple it must be given here as well.
+    pub async fn authenticate(
+        &self,
+        code: &str,
+        nonce: Option<&str>,
+    ) -> Result<IdToken, PlatformSigninError> {
+        let params = [
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("code", code),
+            ("grant_type", "authorization_code"),
+        ];
+        let token: AuthTokenResponse = self.request_token(&params).await?;
+        let id_token = self.verify(&token.id_token).await?;
+        if let Some(nonce) = nonce {
+            if id_token.nonce.as_deref() != Some(nonce) {
+                return Err(PlatformSigninError::ValidationError);
+            }
+        }
         Ok(id_token)
     }
 
-    pub fn validate(&self, refresh_token: String) -> Result<IdToken, PlatformSigninError> {
-        let body = format!(
-            "client_id:{}\nclient_secret:{}\nrefresh_token:{}\ngrant_type:{}",
-            self.client_id, self.client_secret, refresh_token, grant_type
-        );
-        let grant_type = "refresh_token";
+    /// Checks that a refresh token previously received from Apple is still valid.
+    pub async fn validate(&self, refresh_token: &str) -> Result<IdToken, PlatformSigninError> {
+        let params = [
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("refresh_token", refresh_token),
+            ("grant_type", "refresh_token"),
+        ];
+        let token: ValidTokenResponse = self.request_token(&params).await?;
+        self.verify(&token.id_token).await
+    }
+
+    async fn request_token<T: serde::de::DeserializeOwned>(
+        &self,
+        params: &[(&str, &str)],
+    ) -> Result<T, PlatformSigninError> {
         let resp = self
             .client
-            .post("https://appleid.apple.com/auth/token")
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(body)
+            .post(&format!("{}/auth/token", self.server))
+            .form(params)
             .send()
             .await
             .map_err(|_| PlatformSigninError::ReqwestError)?;
-        let token: ValidTokenResponse = if resp.status() == 200 {
-            let text = resp
-                .text()
-                .await
-                .map_err(|_| PlatformSigninError::ReqwestError)?;
-            dbg!(&text);
-            serde_json::from_str(&text).map_err(|_| PlatformSigninError::Serialize)?
-        } else {
+        if resp.status() != 200 {
             return Err(PlatformSigninError::BadRequest);
-        };
-        let validation = Validation {
-            algorithms: vec![Algorithm::ES256],
+        }
+        let text = resp
+            .text()
+            .await
+            .map_err(|_| PlatformSigninError::ReqwestError)?;
+        serde_json::from_str(&text).map_err(|_| PlatformSigninError::Serialize)
+    }
+
+    /// Verifies the signature of an id token against Apple's published keys as well as its
+    /// issuer, audience and expiry.
+    async fn verify(&self, id_token: &str) -> Result<IdToken, PlatformSigninError> {
+        let header = decode_header(id_token).map_err(|_| PlatformSigninError::ValidationError)?;
+        let kid = header.kid.ok_or(PlatformSigninError::ValidationError)?;
+        let key = fetch_key(&self.client, &format!("{}/auth/keys", self.server), &kid).await?;
+        let mut validation = Validation {
+            algorithms: vec![Algorithm::RS256],
+            iss: Some(self.server.clone()),
             ..Default::default()
         };
-        let token_data = decode::<IdToken>(
-            &token.id_token,
-            &DecodingKey::from_base64_secret(&code)
-                .map_err(|_| PlatformSigninError::SecretError)?,
-            &validation,
-        )
-        .map_err(|_| PlatformSigninError::ValidationError)?;
-        let id_token = token_data.claims;
-        Ok(id_token)
+        validation.set_audience(&[&self.client_id]);
+        let token_data = decode::<IdToken>(id_token, &key, &validation)
+            .map_err(|_| PlatformSigninError::ValidationError)?;
+        Ok(token_data.claims)
     }
 }
 
@@ -130,6 +200,7 @@ pub struct Client {
     client: reqwest::Client,
     client_id: String,
     client_secret: String,
+    server: String,
 }
 
 #[derive(Debug, Clone)]
@@ -156,36 +227,41 @@ pub const APPLE_SERVER: &str = "https://appleid.apple.com";
 pub const EXPIRATION_TIME_SECS: i64 = 1200;
 
 impl Client {
+    /// `server` is the base url of Apple's identity service, normally `APPLE_SERVER`. It is both
+    /// where requests are sent and the expected issuer of id tokens.
     pub fn new(
         client_id: String,
         apple_private_key: String,
         apple_kid: String,
         team_id: String,
+        server: String,
     ) -> Result<Self, PlatformSigninError> {
         let client = reqwest::Client::builder()
             .build()
             .map_err(|_| PlatformSigninError::ReqwestError)?;
         let mut header = Header::new(Algorithm::ES256);
         header.kid = Some(apple_kid);
-        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let now = Utc::now().timestamp();
         let claims = Claims {
             iss: team_id,
             iat: now,
             exp: now + EXPIRATION_TIME_SECS,
-            aud: APPLE_SERVER.to_string(),
+            aud: server.clone(),
             sub: client_id.clone(),
         };
 
         let client_secret = encode(
             &header,
             &claims,
-            &EncodingKey::from_ec_pem(apple_private_key.as_ref()).map_err(|_| PlatformSigninError::JwtError)?,
+            &EncodingKey::from_ec_pem(apple_private_key.as_ref())
+                .map_err(|_| PlatformSigninError::JwtError)?,
         )
         .map_err(|_| PlatformSigninError::JwtError)?;
         Ok(Self {
             client,
             client_id,
             client_secret,
+            server: server.trim_end_matches('/').to_string(),
         })
     }
 }
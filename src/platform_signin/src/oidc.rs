@@ -0,0 +1,126 @@
+use crate::{bool_or_string, fetch_key, PlatformSigninError};
+use jsonwebtoken::{decode, decode_header, Algorithm, Validation};
+use serde::{Deserialize, Serialize};
+
+/// The claims of an OpenID Connect id token that we make use of.
+/// https://openid.net/specs/openid-connect-core-1_0.html#IDToken
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OidcIdToken {
+    pub iss: String,
+    pub sub: String,
+    pub exp: i64,
+    #[serde(default)]
+    pub nonce: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default, deserialize_with = "bool_or_string")]
+    pub email_verified: bool,
+    #[serde(default)]
+    pub given_name: Option<String>,
+    #[serde(default)]
+    pub family_name: Option<String>,
+}
+
+impl OidcIdToken {
+    /// The email if the issuer has verified it.
+    pub fn verified_email(&self) -> Option<&str> {
+        if self.email_verified {
+            self.email.as_deref()
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Discovery {
+    jwks_uri: String,
+}
+
+/// Verifies id tokens issued by an OpenID Connect provider, e.g. Google, to one of our clients.
+#[derive(Debug)]
+pub struct OidcClient {
+    client: reqwest::Client,
+    issuers: Vec<String>,
+    audiences: Vec<String>,
+    jwks_uri: String,
+}
+
+impl OidcClient {
+    /// `issuers` are the accepted values of the `iss` claim and `audiences` our client ids at the
+    /// provider.
+    pub fn new(
+        issuers: Vec<String>,
+        audiences: Vec<String>,
+        jwks_uri: String,
+    ) -> Result<Self, PlatformSigninError> {
+        let client = reqwest::Client::builder()
+            .build()
+            .map_err(|_| PlatformSigninError::ReqwestError)?;
+        Ok(Self {
+            client,
+            issuers,
+            audiences,
+            jwks_uri,
+        })
+    }
+
+    /// Like `new` but looks up the key set through the discovery document of the first issuer.
+    pub async fn discover(
+        issuers: Vec<String>,
+        audiences: Vec<String>,
+    ) -> Result<Self, PlatformSigninError> {
+        let mut oidc = Self::new(issuers, audiences, String::new())?;
+        let issuer = oidc
+            .issuers
+            .first()
+            .ok_or(PlatformSigninError::BadRequest)?;
+        let resp = oidc
+            .client
+            .get(&format!(
+                "{}/.well-known/openid-configuration",
+                issuer.trim_end_matches('/')
+            ))
+            .send()
+            .await
+            .map_err(|_| PlatformSigninError::ReqwestError)?;
+        if resp.status() != 200 {
+            return Err(PlatformSigninError::BadRequest);
+        }
+        let text = resp
+            .text()
+            .await
+            .map_err(|_| PlatformSigninError::ReqwestError)?;
+        let discovery: Discovery =
+            serde_json::from_str(&text).map_err(|_| PlatformSigninError::Serialize)?;
+        oidc.jwks_uri = discovery.jwks_uri;
+        Ok(oidc)
+    }
+
+    /// Verifies the signature, issuer, audience and expiry of an id token. If the app passed a
+    /// nonce to the provider it must be given here as well.
+    pub async fn verify(
+        &self,
+        id_token: &str,
+        nonce: Option<&str>,
+    ) -> Result<OidcIdToken, PlatformSigninError> {
+        let header = decode_header(id_token).map_err(|_| PlatformSigninError::ValidationError)?;
+        let kid = header.kid.ok_or(PlatformSigninError::ValidationError)?;
+        let key = fetch_key(&self.client, &self.jwks_uri, &kid).await?;
+        // NOTE: Providers may use several spellings of their issuer, so it is checked below.
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_audience(&self.audiences);
+        let token_data = decode::<OidcIdToken>(id_token, &key, &validation)
+            .map_err(|_| PlatformSigninError::ValidationError)?;
+        let id_token = token_data.claims;
+        if !self.issuers.contains(&id_token.iss) {
+            return Err(PlatformSigninError::ValidationError);
+        }
+        if let Some(nonce) = nonce {
+            if id_token.nonce.as_deref() != Some(nonce) {
+                return Err(PlatformSigninError::ValidationError);
+            }
+        }
+        Ok(id_token)
+    }
+}
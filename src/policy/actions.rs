@@ -0,0 +1,358 @@
+use super::{Action, Grant};
+use crate::models::RoleFlags;
+
+const OWNER: &[Grant] = &[Grant::Owner];
+
+const CONTENT_ADMIN: &[Grant] = &[
+    Grant::Office(RoleFlags::OFFICE_CONTENT_ADMIN),
+    Grant::Global(RoleFlags::GLOBAL_CONTENT_ADMIN),
+];
+
+const PERSONNEL_ADMIN: &[Grant] = &[
+    Grant::Office(RoleFlags::OFFICE_PERSONNEL_ADMIN),
+    Grant::Global(RoleFlags::GLOBAL_PERSONNEL_ADMIN),
+];
+
+const BILLING_ADMIN: &[Grant] = &[
+    Grant::Office(RoleFlags::OFFICE_BILLING_ADMIN),
+    Grant::Global(RoleFlags::GLOBAL_BILLING_ADMIN),
+];
+
+const OWNER_OR_PERSONNEL_ADMIN: &[Grant] = &[
+    Grant::Owner,
+    Grant::Office(RoleFlags::OFFICE_PERSONNEL_ADMIN),
+    Grant::Global(RoleFlags::GLOBAL_PERSONNEL_ADMIN),
+];
+
+// Office grants are left out of actions that take over or remove another account. Every user is
+// in the default office, so its personnel admins would otherwise hold them over every account.
+const OWNER_OR_GLOBAL_PERSONNEL_ADMIN: &[Grant] = &[
+    Grant::Owner,
+    Grant::Global(RoleFlags::GLOBAL_PERSONNEL_ADMIN),
+];
+
+const OWNER_OR_GLOBAL_CONTENT_ADMIN: &[Grant] =
+    &[Grant::Owner, Grant::Global(RoleFlags::GLOBAL_CONTENT_ADMIN)];
+
+const GLOBAL_PERSONNEL_ADMIN: &[Grant] = &[Grant::Global(RoleFlags::GLOBAL_PERSONNEL_ADMIN)];
+
+const GLOBAL_BILLING_ADMIN: &[Grant] = &[Grant::Global(RoleFlags::GLOBAL_BILLING_ADMIN)];
+
+const OFFICE_ADMIN: &[Grant] = &[
+    Grant::Office(RoleFlags::OFFICE_CONTENT_ADMIN),
+    Grant::Office(RoleFlags::OFFICE_BILLING_ADMIN),
+    Grant::Office(RoleFlags::OFFICE_PERSONNEL_ADMIN),
+    Grant::Global(RoleFlags::GLOBAL_CONTENT_ADMIN),
+    Grant::Global(RoleFlags::GLOBAL_BILLING_ADMIN),
+    Grant::Global(RoleFlags::GLOBAL_PERSONNEL_ADMIN),
+];
+
+const GLOBAL_ADMIN: &[Grant] = &[
+    Grant::Global(RoleFlags::GLOBAL_CONTENT_ADMIN),
+    Grant::Global(RoleFlags::GLOBAL_BILLING_ADMIN),
+    Grant::Global(RoleFlags::GLOBAL_PERSONNEL_ADMIN),
+];
+
+// Users.
+
+pub const USER_GET: Action = Action {
+    name: "user.get",
+    grants: OWNER_OR_PERSONNEL_ADMIN,
+};
+
+pub const USER_PUT: Action = Action {
+    name: "user.put",
+    grants: OWNER,
+};
+
+pub const USER_DELETE: Action = Action {
+    name: "user.delete",
+    grants: OWNER_OR_GLOBAL_PERSONNEL_ADMIN,
+};
+
+pub const USER_POLL: Action = Action {
+    name: "user.poll",
+    grants: OWNER,
+};
+
+// Covers the email, password, identities, two factor authentication and devices of the account.
+pub const USER_CREDENTIALS_PUT: Action = Action {
+    name: "user.credentials.put",
+    grants: OWNER,
+};
+
+// Setting the password without knowing the old one.
+pub const USER_PASSWORD_RESET: Action = Action {
+    name: "user.password.reset",
+    grants: GLOBAL_PERSONNEL_ADMIN,
+};
+
+pub const USER_SIGNOUT: Action = Action {
+    name: "user.signout",
+    grants: OWNER,
+};
+
+pub const USER_SESSIONS_GET: Action = Action {
+    name: "user.sessions.get",
+    grants: OWNER_OR_PERSONNEL_ADMIN,
+};
+
+pub const USER_SESSIONS_DELETE: Action = Action {
+    name: "user.sessions.delete",
+    grants: OWNER_OR_GLOBAL_PERSONNEL_ADMIN,
+};
+
+// Upgrading the guest in place to an account with an email or a provider identity.
+pub const GUEST_UPGRADE: Action = Action {
+    name: "guest.upgrade",
+    grants: OWNER,
+};
+
+// Issuing a token to act as the user, see `IMPERSONATION_BLOCKED`.
+pub const USER_IMPERSONATE: Action = Action {
+    name: "user.impersonate",
+    grants: GLOBAL_PERSONNEL_ADMIN,
+};
+
+// Granting roles for the resource's office.
+pub const OFFICE_ROLES_PUT: Action = Action {
+    name: "office.roles.put",
+    grants: PERSONNEL_ADMIN,
+};
+
+pub const OFFICE_INVITATIONS_MANAGE: Action = Action {
+    name: "office.invitations.manage",
+    grants: PERSONNEL_ADMIN,
+};
+
+// Accepting an invitation sent to the user's email.
+pub const INVITATION_ACCEPT: Action = Action {
+    name: "invitation.accept",
+    grants: OWNER,
+};
+
+// Creating, listing and revoking the join codes of the resource's office.
+pub const OFFICE_JOIN_CODES_MANAGE: Action = Action {
+    name: "office.join_codes.manage",
+    grants: PERSONNEL_ADMIN,
+};
+
+// Adding a user to the resource's office.
+pub const USER_OFFICE_PUT: Action = Action {
+    name: "user.office.put",
+    grants: PERSONNEL_ADMIN,
+};
+
+// Removing a user from the resource's office, the user may leave by themselves.
+pub const USER_OFFICE_DELETE: Action = Action {
+    name: "user.office.delete",
+    grants: OWNER_OR_PERSONNEL_ADMIN,
+};
+
+pub const GLOBAL_ROLES_PUT: Action = Action {
+    name: "global.roles.put",
+    grants: GLOBAL_PERSONNEL_ADMIN,
+};
+
+pub const SERVICE_ACCOUNTS_MANAGE: Action = Action {
+    name: "service_accounts.manage",
+    grants: GLOBAL_PERSONNEL_ADMIN,
+};
+
+pub const SECURITY_REPORTS_GET: Action = Action {
+    name: "security_reports.get",
+    grants: GLOBAL_PERSONNEL_ADMIN,
+};
+
+// Reading the audit log of the resource's office.
+pub const OFFICE_AUDIT_GET: Action = Action {
+    name: "office.audit.get",
+    grants: OFFICE_ADMIN,
+};
+
+// Reading the audit log across offices.
+pub const AUDIT_GET: Action = Action {
+    name: "audit.get",
+    grants: GLOBAL_ADMIN,
+};
+
+pub const SCHEDULED_JOBS_RUN: Action = Action {
+    name: "scheduled_jobs.run",
+    grants: &[Grant::Global(RoleFlags::SCHEDULED_JOBS)],
+};
+
+// Subscriptions, the resource is owned by the subscriber and belongs to the subscribed office.
+
+pub const SUBSCRIPTION_GET: Action = Action {
+    name: "subscription.get",
+    grants: OWNER_OR_PERSONNEL_ADMIN,
+};
+
+pub const SUBSCRIPTION_POST: Action = Action {
+    name: "subscription.post",
+    grants: OWNER,
+};
+
+pub const OFFICE_PRODUCTS_GET: Action = Action {
+    name: "office.products.get",
+    grants: BILLING_ADMIN,
+};
+
+// Covers adding, changing and removing the office's products in the catalog. Store products are
+// not tied to an office, all Apple products even share one bundle id, so binding one to an office
+// is only granted globally.
+pub const OFFICE_PRODUCTS_MANAGE: Action = Action {
+    name: "office.products.manage",
+    grants: GLOBAL_BILLING_ADMIN,
+};
+
+// Offices and their content.
+
+pub const OFFICE_POST: Action = Action {
+    name: "office.post",
+    grants: &[Grant::Global(RoleFlags::GLOBAL_CONTENT_ADMIN)],
+};
+
+pub const OFFICE_PUT: Action = Action {
+    name: "office.put",
+    grants: CONTENT_ADMIN,
+};
+
+pub const OFFICE_DELETE: Action = Action {
+    name: "office.delete",
+    grants: CONTENT_ADMIN,
+};
+
+pub const OFFICE_RESTORE: Action = Action {
+    name: "office.restore",
+    grants: CONTENT_ADMIN,
+};
+
+pub const OFFICE_SETTINGS_PUT: Action = Action {
+    name: "office.settings.put",
+    grants: CONTENT_ADMIN,
+};
+
+pub const OFFICE_POLL: Action = Action {
+    name: "office.poll",
+    grants: CONTENT_ADMIN,
+};
+
+pub const SERIES_POST: Action = Action {
+    name: "series.post",
+    grants: CONTENT_ADMIN,
+};
+
+pub const SERIES_PUT: Action = Action {
+    name: "series.put",
+    grants: CONTENT_ADMIN,
+};
+
+pub const SERIES_DELETE: Action = Action {
+    name: "series.delete",
+    grants: CONTENT_ADMIN,
+};
+
+pub const EPISODE_POST: Action = Action {
+    name: "episode.post",
+    grants: CONTENT_ADMIN,
+};
+
+pub const EPISODE_PUT: Action = Action {
+    name: "episode.put",
+    grants: CONTENT_ADMIN,
+};
+
+pub const EPISODE_DELETE: Action = Action {
+    name: "episode.delete",
+    grants: CONTENT_ADMIN,
+};
+
+pub const CATEGORY_POST: Action = Action {
+    name: "category.post",
+    grants: CONTENT_ADMIN,
+};
+
+pub const CATEGORY_PUT: Action = Action {
+    name: "category.put",
+    grants: CONTENT_ADMIN,
+};
+
+pub const CATEGORY_DELETE: Action = Action {
+    name: "category.delete",
+    grants: CONTENT_ADMIN,
+};
+
+pub const RECOMMENDATION_POST: Action = Action {
+    name: "recommendation.post",
+    grants: CONTENT_ADMIN,
+};
+
+pub const RECOMMENDATION_PUT: Action = Action {
+    name: "recommendation.put",
+    grants: CONTENT_ADMIN,
+};
+
+pub const RECOMMENDATION_DELETE: Action = Action {
+    name: "recommendation.delete",
+    grants: CONTENT_ADMIN,
+};
+
+// Listening data, owned by the listening user.
+
+pub const EPISODE_METADATA_GET: Action = Action {
+    name: "episode_metadata.get",
+    grants: OWNER,
+};
+
+pub const EPISODE_METADATA_POST: Action = Action {
+    name: "episode_metadata.post",
+    grants: OWNER,
+};
+
+pub const EPISODE_METADATA_PUT: Action = Action {
+    name: "episode_metadata.put",
+    grants: OWNER,
+};
+
+pub const EPISODE_METADATA_DELETE: Action = Action {
+    name: "episode_metadata.delete",
+    grants: OWNER_OR_GLOBAL_CONTENT_ADMIN,
+};
+
+pub const SERIES_USER_DATA_GET: Action = Action {
+    name: "series_user_data.get",
+    grants: OWNER,
+};
+
+pub const SERIES_USER_DATA_POST: Action = Action {
+    name: "series_user_data.post",
+    grants: OWNER,
+};
+
+pub const SERIES_USER_DATA_PUT: Action = Action {
+    name: "series_user_data.put",
+    grants: OWNER,
+};
+
+pub const SERIES_USER_DATA_DELETE: Action = Action {
+    name: "series_user_data.delete",
+    grants: OWNER_OR_GLOBAL_CONTENT_ADMIN,
+};
+
+// Actions never allowed with an impersonation token, whatever the impersonated user's grants.
+pub const IMPERSONATION_BLOCKED: &[Action] = &[
+    USER_CREDENTIALS_PUT,
+    USER_PASSWORD_RESET,
+    USER_IMPERSONATE,
+    OFFICE_ROLES_PUT,
+    OFFICE_INVITATIONS_MANAGE,
+    OFFICE_JOIN_CODES_MANAGE,
+    USER_OFFICE_PUT,
+    INVITATION_ACCEPT,
+    GUEST_UPGRADE,
+    GLOBAL_ROLES_PUT,
+    SERVICE_ACCOUNTS_MANAGE,
+    SUBSCRIPTION_POST,
+    OFFICE_PRODUCTS_MANAGE,
+];
@@ -0,0 +1,21 @@
+use super::{Action, Grant, Resource, IMPERSONATION_BLOCKED};
+use crate::models::Claims;
+
+/// Whether any of the action's grants applies to the claims for the resource. Subjects are
+/// matched exactly.
+pub fn allows(claims: &Claims, action: Action, resource: &Resource) -> bool {
+    if claims.act.is_some() && IMPERSONATION_BLOCKED.contains(&action) {
+        return false;
+    }
+    action.grants.iter().any(|grant| match grant {
+        Grant::Owner => resource.owner == Some(claims.sub.as_str()),
+        Grant::Office(flag) => claims.rol.iter().any(|role| {
+            role.flg.intersects(*flag)
+                && role
+                    .sub
+                    .as_deref()
+                    .is_some_and(|sub| resource.offices.contains(&sub))
+        }),
+        Grant::Global(flag) => claims.rol.iter().any(|role| role.flg.intersects(*flag)),
+    })
+}
@@ -0,0 +1,20 @@
+use super::{allows, Action, Resource};
+use crate::fault::Fault;
+use crate::models::Claims;
+use warp::reject;
+
+/// Fails with `Fault::Forbidden` unless the claims are granted the action on the resource.
+pub fn authorize(
+    claims: &Claims,
+    action: Action,
+    resource: &Resource,
+) -> Result<(), warp::Rejection> {
+    if allows(claims, action, resource) {
+        Ok(())
+    } else {
+        Err(reject::custom(Fault::Forbidden(format!(
+            "Caller may not {}.",
+            action.name
+        ))))
+    }
+}
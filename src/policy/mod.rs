@@ -0,0 +1,37 @@
+//! Authorization of actions. Each action declares which roles and relationships to the resource
+//! grant it, handlers check the caller with `authorize`.
+
+use crate::models::RoleFlags;
+
+/// A way to be granted an action.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Grant {
+    // The caller is the user owning the resource.
+    Owner,
+    // The caller has a role with the flag whose subject is one of the resource's offices.
+    Office(RoleFlags),
+    // The caller has a role with the flag, whatever its subject.
+    Global(RoleFlags),
+}
+
+/// Something a caller can do, e.g. `user.delete`, granted by any one of its grants.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Action {
+    pub name: &'static str,
+    pub grants: &'static [Grant],
+}
+
+mod actions;
+pub use actions::*;
+
+mod resource;
+pub use resource::Resource;
+
+mod allows;
+pub use allows::allows;
+
+mod authorize;
+pub use authorize::authorize;
+
+#[cfg(test)]
+mod tests;
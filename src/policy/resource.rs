@@ -0,0 +1,45 @@
+use crate::models::User;
+
+/// What an action is performed on, as far as authorization is concerned.
+#[derive(Debug, Default, Clone)]
+pub struct Resource<'a> {
+    // Id of the user owning the resource.
+    pub owner: Option<&'a str>,
+
+    // Ids of the offices the resource belongs to.
+    pub offices: Vec<&'a str>,
+}
+
+impl<'a> Resource<'a> {
+    /// A resource owned by no one and belonging to no office, only granted globally.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn owner(user_id: &'a str) -> Self {
+        Self {
+            owner: Some(user_id),
+            offices: vec![],
+        }
+    }
+
+    pub fn office(office_id: &'a str) -> Self {
+        Self {
+            owner: None,
+            offices: vec![office_id],
+        }
+    }
+
+    /// The user's account, owned by the user and belonging to the user's offices.
+    pub fn user(user: &'a User) -> Self {
+        Self {
+            owner: Some(&user.id),
+            offices: user.office_ids.iter().map(String::as_str).collect(),
+        }
+    }
+
+    pub fn in_office(mut self, office_id: &'a str) -> Self {
+        self.offices.push(office_id);
+        self
+    }
+}
@@ -0,0 +1,510 @@
+use super::*;
+use crate::models::{Claims, Role, RoleFlags};
+use crate::roles;
+use crate::util::has_role;
+use chrono::Utc;
+
+struct Case {
+    name: &'static str,
+    caller: &'static str,
+    roles: Vec<Role>,
+    action: Action,
+    owner: Option<&'static str>,
+    offices: Vec<&'static str>,
+    allowed: bool,
+}
+
+fn role(flg: RoleFlags, sub: Option<&str>) -> Role {
+    Role {
+        flg,
+        sub: sub.map(String::from),
+    }
+}
+
+fn cases() -> Vec<Case> {
+    vec![
+        Case {
+            name: "user deletes themselves",
+            caller: "u1",
+            roles: vec![],
+            action: USER_DELETE,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "user deletes another user",
+            caller: "u2",
+            roles: vec![],
+            action: USER_DELETE,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "office personnel admin deletes a user of the office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_PERSONNEL_ADMIN, Some("abcd"))],
+            action: USER_DELETE,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "office personnel admin gets a user of the office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_PERSONNEL_ADMIN, Some("abcd"))],
+            action: USER_GET,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "office personnel admin gets a user of an office with a longer id",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_PERSONNEL_ADMIN, Some("abc"))],
+            action: USER_GET,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "global personnel admin deletes a user",
+            caller: "admin",
+            roles: vec![role(RoleFlags::GLOBAL_PERSONNEL_ADMIN, None)],
+            action: USER_DELETE,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "office content admin deletes a user of the office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_CONTENT_ADMIN, Some("abcd"))],
+            action: USER_DELETE,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "office role without subject",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_PERSONNEL_ADMIN, None)],
+            action: USER_GET,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "user resets their own password",
+            caller: "u1",
+            roles: vec![],
+            action: USER_PASSWORD_RESET,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "office personnel admin resets the password of a user of the office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_PERSONNEL_ADMIN, Some("abcd"))],
+            action: USER_PASSWORD_RESET,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "global personnel admin resets the password of a user",
+            caller: "admin",
+            roles: vec![role(RoleFlags::GLOBAL_PERSONNEL_ADMIN, None)],
+            action: USER_PASSWORD_RESET,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "office personnel admin signs out a user of the office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_PERSONNEL_ADMIN, Some("abcd"))],
+            action: USER_SESSIONS_DELETE,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "user signs out their own session",
+            caller: "u1",
+            roles: vec![],
+            action: USER_SESSIONS_DELETE,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "office personnel admin resets the password of a user of another office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_PERSONNEL_ADMIN, Some("efgh"))],
+            action: USER_PASSWORD_RESET,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "content admin puts series of the office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_CONTENT_ADMIN, Some("abcd"))],
+            action: SERIES_PUT,
+            owner: None,
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "content admin puts series of an office with a longer id",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_CONTENT_ADMIN, Some("abc"))],
+            action: SERIES_PUT,
+            owner: None,
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "content admin puts series of an office with a shorter id",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_CONTENT_ADMIN, Some("abcd"))],
+            action: SERIES_PUT,
+            owner: None,
+            offices: vec!["abc"],
+            allowed: false,
+        },
+        Case {
+            name: "role with several flags puts series",
+            caller: "admin",
+            roles: vec![role(
+                RoleFlags::OFFICE_PERSONNEL_ADMIN | RoleFlags::OFFICE_CONTENT_ADMIN,
+                Some("abcd"),
+            )],
+            action: SERIES_PUT,
+            owner: None,
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "global content admin puts series",
+            caller: "admin",
+            roles: vec![role(RoleFlags::GLOBAL_CONTENT_ADMIN, None)],
+            action: SERIES_PUT,
+            owner: None,
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "office content admin posts an office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_CONTENT_ADMIN, Some("abcd"))],
+            action: OFFICE_POST,
+            owner: None,
+            offices: vec![],
+            allowed: false,
+        },
+        Case {
+            name: "global content admin posts an office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::GLOBAL_CONTENT_ADMIN, None)],
+            action: OFFICE_POST,
+            owner: None,
+            offices: vec![],
+            allowed: true,
+        },
+        Case {
+            name: "office content admin restores another office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_CONTENT_ADMIN, Some("efgh"))],
+            action: OFFICE_RESTORE,
+            owner: None,
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "subscriber reads their subscription",
+            caller: "u1",
+            roles: vec![],
+            action: SUBSCRIPTION_GET,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "personnel admin of the subscribed office reads a subscription",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_PERSONNEL_ADMIN, Some("abcd"))],
+            action: SUBSCRIPTION_GET,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "personnel admin of another office reads a subscription",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_PERSONNEL_ADMIN, Some("efgh"))],
+            action: SUBSCRIPTION_GET,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "user posts a subscription for another user",
+            caller: "u2",
+            roles: vec![],
+            action: SUBSCRIPTION_POST,
+            owner: Some("u1"),
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "office billing admin manages the products of the office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_BILLING_ADMIN, Some("abcd"))],
+            action: OFFICE_PRODUCTS_MANAGE,
+            owner: None,
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "office billing admin lists the products of the office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_BILLING_ADMIN, Some("abcd"))],
+            action: OFFICE_PRODUCTS_GET,
+            owner: None,
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "global billing admin manages the products of an office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::GLOBAL_BILLING_ADMIN, None)],
+            action: OFFICE_PRODUCTS_MANAGE,
+            owner: None,
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "office content admin manages the products of the office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_CONTENT_ADMIN, Some("abcd"))],
+            action: OFFICE_PRODUCTS_MANAGE,
+            owner: None,
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "office billing admin reads the audit log of the office",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_BILLING_ADMIN, Some("abcd"))],
+            action: OFFICE_AUDIT_GET,
+            owner: None,
+            offices: vec!["abcd"],
+            allowed: true,
+        },
+        Case {
+            name: "craftsman reads the audit log of the office",
+            caller: "u1",
+            roles: vec![role(RoleFlags::CRAFTSMAN, Some("abcd"))],
+            action: OFFICE_AUDIT_GET,
+            owner: None,
+            offices: vec!["abcd"],
+            allowed: false,
+        },
+        Case {
+            name: "office personnel admin reads the global audit log",
+            caller: "admin",
+            roles: vec![role(RoleFlags::OFFICE_PERSONNEL_ADMIN, Some("abcd"))],
+            action: AUDIT_GET,
+            owner: None,
+            offices: vec![],
+            allowed: false,
+        },
+        Case {
+            name: "global content admin reads the global audit log",
+            caller: "admin",
+            roles: vec![role(RoleFlags::GLOBAL_CONTENT_ADMIN, None)],
+            action: AUDIT_GET,
+            owner: None,
+            offices: vec![],
+            allowed: true,
+        },
+        Case {
+            name: "user reads listening data of a user with a longer id",
+            caller: "u1",
+            roles: vec![],
+            action: EPISODE_METADATA_GET,
+            owner: Some("u10"),
+            offices: vec![],
+            allowed: false,
+        },
+        Case {
+            name: "service account runs scheduled jobs",
+            caller: "cron",
+            roles: vec![role(RoleFlags::SCHEDULED_JOBS, None)],
+            action: SCHEDULED_JOBS_RUN,
+            owner: None,
+            offices: vec![],
+            allowed: true,
+        },
+        Case {
+            name: "global personnel admin runs scheduled jobs",
+            caller: "admin",
+            roles: vec![role(RoleFlags::GLOBAL_PERSONNEL_ADMIN, None)],
+            action: SCHEDULED_JOBS_RUN,
+            owner: None,
+            offices: vec![],
+            allowed: false,
+        },
+    ]
+}
+
+#[test]
+fn allows_matches_table() {
+    for case in cases() {
+        let claims = Claims::new(case.caller, Utc::now(), &case.roles);
+        let resource = Resource {
+            owner: case.owner,
+            offices: case.offices.clone(),
+        };
+        assert_eq!(
+            allows(&claims, case.action, &resource),
+            case.allowed,
+            "{}",
+            case.name
+        );
+        assert_eq!(
+            authorize(&claims, case.action, &resource).is_ok(),
+            case.allowed,
+            "{}",
+            case.name
+        );
+    }
+}
+
+// `has_role` is what the handlers of `third_pact::model` call, with the url's owner or office id
+// as subject.
+#[test]
+fn has_role_matches_table() {
+    let cases = vec![
+        (
+            "owner gets series user data",
+            "u1",
+            vec![],
+            SERIES_USER_DATA_GET,
+            Some("u1"),
+            true,
+        ),
+        (
+            "office admin with the user id as subject gets series user data",
+            "admin",
+            vec![role(RoleFlags::OFFICE_CONTENT_ADMIN, Some("u1"))],
+            SERIES_USER_DATA_GET,
+            Some("u1"),
+            false,
+        ),
+        (
+            "content admin puts an episode of the office",
+            "admin",
+            vec![role(RoleFlags::OFFICE_CONTENT_ADMIN, Some("abcd"))],
+            EPISODE_PUT,
+            Some("abcd"),
+            true,
+        ),
+        (
+            "content admin puts an episode of an office with a longer id",
+            "admin",
+            vec![role(RoleFlags::OFFICE_CONTENT_ADMIN, Some("abc"))],
+            EPISODE_PUT,
+            Some("abcd"),
+            false,
+        ),
+        (
+            "user whose id is the office id puts an episode",
+            "abcd",
+            vec![],
+            EPISODE_PUT,
+            Some("abcd"),
+            false,
+        ),
+        (
+            "global content admin posts an office without subject",
+            "admin",
+            vec![role(RoleFlags::GLOBAL_CONTENT_ADMIN, None)],
+            OFFICE_POST,
+            None,
+            true,
+        ),
+    ];
+    for (name, caller, roles, action, sub, allowed) in cases {
+        let claims = Claims::new(caller, Utc::now(), &roles);
+        assert_eq!(has_role(sub, &claims, action), allowed, "{}", name);
+    }
+}
+
+// Impersonation tokens carry the impersonated user's roles but not every action they grant.
+#[test]
+fn impersonation_is_blocked_from_sensitive_actions() {
+    let roles = vec![role(
+        RoleFlags::OFFICE_PERSONNEL_ADMIN | RoleFlags::OFFICE_CONTENT_ADMIN,
+        Some("abcd"),
+    )];
+    let claims = Claims {
+        act: Some(String::from("admin")),
+        wrt: true,
+        ..Claims::new("u1", Utc::now(), &roles)
+    };
+    let resource = Resource::owner("u1").in_office("abcd");
+    for action in IMPERSONATION_BLOCKED {
+        assert!(!allows(&claims, *action, &resource), "{}", action.name);
+    }
+    assert!(allows(&claims, USER_POLL, &resource));
+}
+
+// Blocked even for an impersonation token that would carry the user's billing admin role.
+#[test]
+fn impersonation_is_blocked_from_managing_products() {
+    let roles = vec![role(RoleFlags::GLOBAL_BILLING_ADMIN, None)];
+    let claims = Claims::new("u1", Utc::now(), &roles);
+    let resource = Resource::office("abcd");
+    assert!(allows(&claims, OFFICE_PRODUCTS_MANAGE, &resource));
+    let claims = Claims {
+        act: Some(String::from("admin")),
+        wrt: true,
+        ..claims
+    };
+    assert!(!allows(&claims, OFFICE_PRODUCTS_MANAGE, &resource));
+}
+
+// Impersonation tokens are issued without the user's office admin flags.
+#[test]
+fn impersonation_drops_office_admin_roles() {
+    let roles = vec![
+        role(
+            RoleFlags::CRAFTSMAN | RoleFlags::OFFICE_CONTENT_ADMIN,
+            Some("abcd"),
+        ),
+        role(RoleFlags::OFFICE_BILLING_ADMIN, Some("efgh")),
+    ];
+    assert_eq!(
+        roles::unmanaged(&roles),
+        vec![role(RoleFlags::CRAFTSMAN, Some("abcd"))]
+    );
+
+    let claims = Claims {
+        act: Some(String::from("admin")),
+        wrt: true,
+        ..Claims::new("u1", Utc::now(), &roles::unmanaged(&roles))
+    };
+    let resource = Resource::owner("u1").in_office("abcd");
+    assert!(allows(&claims, USER_POLL, &resource));
+    assert!(!allows(&claims, SERIES_PUT, &resource));
+    assert!(!allows(
+        &claims,
+        OFFICE_PRODUCTS_MANAGE,
+        &Resource::office("efgh")
+    ));
+}
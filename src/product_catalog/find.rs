@@ -0,0 +1,35 @@
+use super::key;
+use crate::models::{Product, ProductKey};
+use crate::{PRODUCT_COLLECTION, PRODUCT_KEY_COLLECTION};
+use cosmos_utils::{get, CosmosErrorKind, CosmosErrorStruct};
+use in_app_purchases::Platform;
+
+/// The catalog's product with the store product id in the app with the bundle id or package name.
+pub async fn find(
+    platform: Platform,
+    product_id: &str,
+    package_name: &str,
+) -> Result<Option<Product>, CosmosErrorStruct> {
+    let id = key(platform, product_id, package_name);
+    let product_key: ProductKey = match get(PRODUCT_KEY_COLLECTION, [&id], &id).await {
+        Ok((product_key, _)) => product_key,
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => return Ok(None),
+            _ => return Err(e),
+        },
+    };
+    match get(
+        PRODUCT_COLLECTION,
+        [&product_key.office_id],
+        &product_key.catalog_id,
+    )
+    .await
+    {
+        Ok((product, _)) => Ok(Some(product)),
+        // The product was removed after the key was released.
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => Ok(None),
+            _ => Err(e),
+        },
+    }
+}
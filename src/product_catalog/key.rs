@@ -0,0 +1,14 @@
+use crate::util;
+use in_app_purchases::Platform;
+use serde_json::json;
+
+/// Id of the `ProductKey` of the store product with the product id in the app with the bundle id
+/// or package name.
+pub fn key(platform: Platform, product_id: &str, package_name: &str) -> String {
+    util::hash_token(&format!(
+        "{} {} {}",
+        json!(platform),
+        json!(package_name),
+        json!(product_id)
+    ))
+}
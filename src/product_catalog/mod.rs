@@ -0,0 +1,24 @@
+//! Which store products entitle to which office. The office of a purchase is always taken from
+//! the catalog, never from the client. Each store product is reserved for a single product of the
+//! catalog by a `ProductKey`.
+
+mod key;
+pub use key::key;
+
+mod find;
+pub use find::find;
+
+mod validate;
+pub use validate::validate;
+
+mod reserve;
+pub use reserve::reserve;
+
+mod release;
+pub use release::release;
+
+mod product_ids;
+pub use product_ids::product_ids;
+
+mod touch_settings;
+pub use touch_settings::touch_settings;
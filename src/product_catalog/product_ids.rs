@@ -0,0 +1,11 @@
+use crate::PRODUCT_COLLECTION;
+use cosmos_utils::{query, CosmosErrorStruct};
+
+/// Store product ids of the office's products in the catalog, for the apps to offer.
+pub async fn product_ids(office_id: &str) -> Result<Vec<String>, CosmosErrorStruct> {
+    let q = format!(
+        "SELECT DISTINCT VALUE p.productId FROM {} p",
+        PRODUCT_COLLECTION
+    );
+    query(PRODUCT_COLLECTION, [&office_id], q, -1).await
+}
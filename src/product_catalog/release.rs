@@ -0,0 +1,32 @@
+use super::key;
+use crate::models::{Product, ProductKey};
+use crate::util::log;
+use crate::PRODUCT_KEY_COLLECTION;
+use cosmos_utils::{delete, get, CosmosErrorKind, CosmosErrorStruct};
+
+/// Releases the product's store product so that it can be added to the catalog again. Only
+/// logs errors, a key left behind keeps the store product reserved.
+pub async fn release(product: &Product) {
+    if let Err(e) = try_release(product).await {
+        log(format!(
+            "Could not release product {} due to {}",
+            product.id, e
+        ));
+    }
+}
+
+async fn try_release(product: &Product) -> Result<(), CosmosErrorStruct> {
+    let id = key(product.platform, &product.product_id, &product.package_name);
+    let (product_key, etag): (ProductKey, _) = match get(PRODUCT_KEY_COLLECTION, [&id], &id).await {
+        Ok(k) => k,
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => return Ok(()),
+            _ => return Err(e),
+        },
+    };
+    // The key belongs to another product of the catalog.
+    if product_key.catalog_id != product.id {
+        return Ok(());
+    }
+    delete(PRODUCT_KEY_COLLECTION, [&id], &id, Some(etag)).await
+}
@@ -0,0 +1,33 @@
+use super::key;
+use crate::fault::Fault;
+use crate::models::{Product, ProductKey};
+use crate::PRODUCT_KEY_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::{insert, CosmosErrorKind};
+use warp::reject;
+
+/// Reserves the product's store product for it, fails if the store product is already in the
+/// catalog.
+pub async fn reserve(product: &Product) -> Result<(), warp::Rejection> {
+    let product_key = ProductKey {
+        id: key(product.platform, &product.product_id, &product.package_name),
+        office_id: product.office_id.clone(),
+        catalog_id: product.id.clone(),
+        created: Utc::now(),
+    };
+    insert(
+        PRODUCT_KEY_COLLECTION,
+        [&product_key.id],
+        &product_key,
+        None,
+    )
+    .await
+    .map_err(|e| match e.kind {
+        CosmosErrorKind::Conflict => reject::custom(Fault::Duplicate(format!(
+            "Product {} is already in the catalog",
+            product.product_id
+        ))),
+        _ => e.into(),
+    })?;
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::models::OfficeSettings;
+use crate::util::log;
+use crate::OFFICE_SETTINGS_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::{insert, modify, CosmosErrorKind, CosmosErrorStruct};
+
+/// Marks the office's settings as modified after its products changed, so that the next polls
+/// send the settings with the new product ids. Settings are created if the office has none. Only
+/// logs errors, the product ids are then sent with the next change of the settings.
+pub async fn touch_settings(office_id: &str) {
+    if let Err(e) = try_touch_settings(office_id).await {
+        log(format!(
+            "Could not touch the settings of office {} due to {}",
+            office_id, e
+        ));
+    }
+}
+
+async fn try_touch_settings(office_id: &str) -> Result<(), CosmosErrorStruct> {
+    let touched = modify(
+        OFFICE_SETTINGS_COLLECTION,
+        [&office_id],
+        office_id,
+        |mut settings: OfficeSettings| {
+            settings.modified = Utc::now();
+            Ok(settings)
+        },
+    )
+    .await;
+    match touched {
+        Ok(_) => Ok(()),
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => {
+                let settings = OfficeSettings {
+                    id: office_id.to_string(),
+                    office_id: office_id.to_string(),
+                    default_language: None,
+                    supported_languages: vec![],
+                    logo_image: None,
+                    hero_image: None,
+                    accent_colours: vec![],
+                    support_email: None,
+                    terms_url: None,
+                    privacy_policy_url: None,
+                    product_ids: vec![],
+                    modified: Utc::now(),
+                };
+                match insert(OFFICE_SETTINGS_COLLECTION, [&office_id], &settings, None).await {
+                    Ok(_) => Ok(()),
+                    Err(e) => match e.kind {
+                        // Created along the way, just as new.
+                        CosmosErrorKind::Conflict => Ok(()),
+                        _ => Err(e),
+                    },
+                }
+            }
+            _ => Err(e),
+        },
+    }
+}
@@ -0,0 +1,31 @@
+use crate::fault::Fault;
+use crate::models::Product;
+use crate::IN_APP_PURCHASES_APPLE_BUNDLE_ID;
+use in_app_purchases::Platform;
+use warp::reject;
+
+/// Checks a product about to be saved. That a store product is only in the catalog once is
+/// checked by `reserve`.
+pub fn validate(product: &Product) -> Result<(), warp::Rejection> {
+    if product.product_id.is_empty() || product.package_name.is_empty() {
+        return Err(reject::custom(Fault::IllegalArgument(String::from(
+            "Product id and package name must not be empty",
+        ))));
+    }
+    // Apple receipts are checked against the app's bundle id, a product under any other bundle id
+    // could never be purchased.
+    if let Platform::Apple = product.platform {
+        if product.package_name != *IN_APP_PURCHASES_APPLE_BUNDLE_ID {
+            return Err(reject::custom(Fault::IllegalArgument(format!(
+                "Apple products must use the bundle id {}",
+                *IN_APP_PURCHASES_APPLE_BUNDLE_ID
+            ))));
+        }
+    }
+    if product.entitlement_days.is_some_and(|d| d <= 0) {
+        return Err(reject::custom(Fault::IllegalArgument(String::from(
+            "Entitlement days must be positive",
+        ))));
+    }
+    Ok(())
+}
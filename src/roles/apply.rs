@@ -0,0 +1,84 @@
+use super::{authorize, difference, normalize};
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::{Claims, Role, RoleFlags, User};
+use crate::policy::{self, Resource};
+use crate::USER_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::{get, query_crosspartition, upsert};
+use warp::reject;
+
+/// Sets the user's roles to what `change` makes of the current ones. The claims must be allowed to
+/// grant and revoke every role that changes, and the last global personnel admin can not lose the
+/// role. Bumps the user's role generation if the roles changed, so that the user's access tokens
+/// are refreshed with the new roles. The change is recorded in the audit log.
+pub async fn apply<F>(
+    user_id: &str,
+    claims: &Claims,
+    request_id: &str,
+    change: F,
+) -> Result<User, warp::Rejection>
+where
+    F: FnOnce(&[Role]) -> Vec<Role>,
+{
+    let (mut user, etag): (User, _) = get(USER_COLLECTION, [user_id], user_id).await?;
+
+    let roles = normalize(change(&user.roles));
+    let granted = difference(&roles, &user.roles);
+    let revoked = difference(&user.roles, &roles);
+    if granted.is_empty() && revoked.is_empty() {
+        return Ok(user);
+    }
+    authorize(claims, &granted)?;
+    authorize(claims, &revoked)?;
+
+    if revoked
+        .iter()
+        .any(|r| r.flg.contains(RoleFlags::GLOBAL_PERSONNEL_ADMIN))
+    {
+        let q = format!(
+            "SELECT VALUE u.id FROM {} u JOIN r IN u.roles \
+             WHERE (r.flg & {}) != 0 AND NOT IS_DEFINED(r.sub) \
+             AND NOT IS_DEFINED(u.deleted) AND u.id != \"{}\"",
+            USER_COLLECTION,
+            RoleFlags::GLOBAL_PERSONNEL_ADMIN.bits(),
+            user.id
+        );
+        let others: Vec<String> = query_crosspartition(USER_COLLECTION, [&()], q, 1, true).await?;
+        if others.is_empty() {
+            return Err(reject::custom(Fault::IllegalState(String::from(
+                "Cannot remove the last global personnel admin.",
+            ))));
+        }
+    }
+
+    let old_user = user.clone();
+    user.roles = roles;
+    user.role_generation += 1;
+    user.modified = Utc::now();
+    upsert(USER_COLLECTION, [user_id], &user, Some(&etag)).await?;
+
+    let changed = granted.iter().chain(&revoked);
+    let action = if changed.clone().any(|r| r.sub.is_none()) {
+        policy::GLOBAL_ROLES_PUT
+    } else {
+        policy::OFFICE_ROLES_PUT
+    };
+    let mut resource = Resource::owner(user_id);
+    for office_id in changed.filter_map(|r| r.sub.as_deref()) {
+        if !resource.offices.contains(&office_id) {
+            resource = resource.in_office(office_id);
+        }
+    }
+    audit::record(
+        request_id,
+        claims,
+        action,
+        user_id,
+        &resource,
+        audit::changes(Some(&old_user), Some(&user)),
+    )
+    .await;
+
+    Ok(user)
+}
@@ -0,0 +1,53 @@
+use super::{GLOBAL_FLAGS, OFFICE_FLAGS};
+use crate::fault::Fault;
+use crate::models::{Claims, Role, RoleFlags};
+use crate::policy::{self, Resource};
+use warp::reject;
+
+/// Checks that the roles are well formed office or global roles, and that the claims may grant
+/// and revoke them.
+/// NOTE: Craftsman roles can not be granted here, for that you should create a craftsman.
+pub fn authorize(claims: &Claims, roles: &[Role]) -> Result<(), warp::Rejection> {
+    for role in roles {
+        if role.flg.intersects(OFFICE_FLAGS) {
+            if (role.flg - OFFICE_FLAGS) != RoleFlags::NONE {
+                return Err(reject::custom(Fault::IllegalArgument(String::from(
+                    "Cannot mix office roles with other roles.",
+                ))));
+            }
+
+            if let Some(sub) = &role.sub {
+                let len = sub.split(' ').count();
+                if len != 1 {
+                    return Err(reject::custom(Fault::IllegalArgument(format!(
+                        "Wrong number of key components for tenant roles subject ({} != 1).",
+                        len,
+                    ))));
+                }
+                policy::authorize(claims, policy::OFFICE_ROLES_PUT, &Resource::office(sub))?;
+            } else {
+                return Err(reject::custom(Fault::IllegalArgument(String::from(
+                    "Must have subject for office roles.",
+                ))));
+            }
+        } else if role.flg.intersects(GLOBAL_FLAGS) {
+            if (role.flg - GLOBAL_FLAGS) != RoleFlags::NONE {
+                return Err(reject::custom(Fault::IllegalArgument(String::from(
+                    "Cannot mix global roles with other roles.",
+                ))));
+            } else if role.sub.is_some() {
+                return Err(reject::custom(Fault::IllegalArgument(String::from(
+                    "Cannot have subject for global roles.",
+                ))));
+            }
+
+            policy::authorize(claims, policy::GLOBAL_ROLES_PUT, &Resource::none())?;
+        } else {
+            return Err(reject::custom(Fault::IllegalArgument(format!(
+                "Unrecognized role flag {}.",
+                role.flg.bits()
+            ))));
+        }
+    }
+    Ok(())
+}
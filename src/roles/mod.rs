@@ -0,0 +1,27 @@
+//! Granting and revoking users' roles. A user's roles are kept normalised, with at most one role
+//! per subject.
+
+use crate::models::RoleFlags;
+
+/// Role flags granted for an office, the office id is the role's subject.
+const OFFICE_FLAGS: RoleFlags = RoleFlags::from_bits_truncate(
+    RoleFlags::OFFICE_CONTENT_ADMIN.bits()
+        | RoleFlags::OFFICE_BILLING_ADMIN.bits()
+        | RoleFlags::OFFICE_PERSONNEL_ADMIN.bits(),
+);
+
+/// Role flags granted globally, without subject.
+const GLOBAL_FLAGS: RoleFlags = RoleFlags::from_bits_truncate(
+    RoleFlags::GLOBAL_CONTENT_ADMIN.bits()
+        | RoleFlags::GLOBAL_BILLING_ADMIN.bits()
+        | RoleFlags::GLOBAL_PERSONNEL_ADMIN.bits(),
+);
+
+mod normalize;
+pub use normalize::{difference, normalize, unmanaged};
+
+mod authorize;
+pub use authorize::authorize;
+
+mod apply;
+pub use apply::apply;
@@ -0,0 +1,40 @@
+use super::{GLOBAL_FLAGS, OFFICE_FLAGS};
+use crate::models::Role;
+
+/// Merges roles with the same subject and drops roles without flags, keeping the order in which
+/// the subjects first appear.
+pub fn normalize(roles: impl IntoIterator<Item = Role>) -> Vec<Role> {
+    let mut normalized: Vec<Role> = vec![];
+    for role in roles {
+        if role.flg.is_empty() {
+            continue;
+        }
+        match normalized.iter_mut().find(|r| r.sub == role.sub) {
+            Some(r) => r.flg |= role.flg,
+            None => normalized.push(role),
+        }
+    }
+    normalized
+}
+
+/// The flags of `roles` that `other` does not have for the same subject.
+pub fn difference(roles: &[Role], other: &[Role]) -> Vec<Role> {
+    normalize(roles.iter().map(|role| {
+        Role {
+            flg: other
+                .iter()
+                .filter(|o| o.sub == role.sub)
+                .fold(role.flg, |flg, o| flg - o.flg),
+            sub: role.sub.clone(),
+        }
+    }))
+}
+
+/// The flags of `roles` that can not be granted through the roles endpoints, e.g. craftsman roles,
+/// which are kept when the roles are replaced.
+pub fn unmanaged(roles: &[Role]) -> Vec<Role> {
+    normalize(roles.iter().map(|role| Role {
+        flg: role.flg - OFFICE_FLAGS - GLOBAL_FLAGS,
+        sub: role.sub.clone(),
+    }))
+}
@@ -0,0 +1,58 @@
+use super::{KEY_SEPARATOR, LAST_USED_RESOLUTION_MINUTES};
+use crate::fault::Fault;
+use crate::models::{Claims, ServiceAccount};
+use crate::util::{self, log};
+use crate::SERVICE_ACCOUNT_COLLECTION;
+use chrono::{Duration, Utc};
+use cosmos_utils::{get, upsert, CosmosErrorKind};
+use warp::reject;
+
+/// Resolves an API key to the claims of its service account. The claims carry the account's roles
+/// and its id as subject, and are never encoded as a token.
+pub async fn authenticate(key: &str) -> Result<Claims, warp::Rejection> {
+    let (account_id, secret) = key
+        .split_once(KEY_SEPARATOR)
+        .ok_or_else(|| reject::custom(Fault::Unauthorized))?;
+
+    let (mut account, etag): (ServiceAccount, _) =
+        match get(SERVICE_ACCOUNT_COLLECTION, [account_id], account_id).await {
+            Ok(a) => a,
+            Err(e) => match e.kind {
+                CosmosErrorKind::NotFound => return Err(reject::custom(Fault::Unauthorized)),
+                _ => return Err(e.into()),
+            },
+        };
+
+    let now = Utc::now();
+    if account.revoked
+        || account.expires.map(|e| e <= now).unwrap_or(false)
+        || account.key_hash != util::hash_token(secret)
+    {
+        return Err(reject::custom(Fault::Unauthorized));
+    }
+
+    let stale = match account.last_used {
+        Some(last_used) => now - last_used > Duration::minutes(LAST_USED_RESOLUTION_MINUTES),
+        None => true,
+    };
+    if stale {
+        // NOTE: Best effort, a concurrent request may already have updated it.
+        account.last_used = Some(now);
+        if let Err(e) = upsert(
+            SERVICE_ACCOUNT_COLLECTION,
+            [&account.id],
+            &account,
+            Some(&etag),
+        )
+        .await
+        {
+            log(format!(
+                "Could not update last used of service account {} due to {}",
+                account.id, e
+            ));
+        }
+    }
+
+    // The expiry of the claims is not checked for service accounts, the request is served now.
+    Ok(Claims::new(&account.id, now, &account.roles))
+}
@@ -0,0 +1,34 @@
+use super::{KEY_SEPARATOR, SECRET_LENGTH};
+use crate::models::{Role, ServiceAccount};
+use crate::util;
+use crate::SERVICE_ACCOUNT_COLLECTION;
+use chrono::{DateTime, Utc};
+use cosmos_utils::insert;
+
+/// Creates a service account and returns it along with its API key. Only the hash of the key is
+/// stored, so the key can not be shown again.
+pub async fn create(
+    name: String,
+    roles: Vec<Role>,
+    expires: Option<DateTime<Utc>>,
+    created_by: &str,
+) -> Result<(ServiceAccount, String), warp::Rejection> {
+    let secret = util::random_string(SECRET_LENGTH);
+    let mut account = ServiceAccount {
+        id: util::new_guid_v4(),
+        name,
+        roles,
+        key_hash: util::hash_token(&secret),
+        expires,
+        last_used: None,
+        revoked: false,
+        created_by: created_by.to_string(),
+        modified: Utc::now(),
+        created: Utc::now(),
+    };
+    insert(SERVICE_ACCOUNT_COLLECTION, [&account.id], &account, None).await?;
+
+    let key = format!("{}{}{}", account.id, KEY_SEPARATOR, secret);
+    account.key_hash = String::new();
+    Ok((account, key))
+}
@@ -0,0 +1,14 @@
+/// Separates the account id from the secret in an API key.
+const KEY_SEPARATOR: char = '.';
+
+/// Length of the secret part of an API key.
+const SECRET_LENGTH: usize = 40;
+
+/// How often the last used time of an account is written, to avoid a write on every request.
+const LAST_USED_RESOLUTION_MINUTES: i64 = 5;
+
+mod create;
+pub use create::create;
+
+mod authenticate;
+pub use authenticate::authenticate;
@@ -0,0 +1,23 @@
+use super::Subject;
+use crate::fault::Fault;
+use crate::models::SigninAttempts;
+use crate::SIGNIN_ATTEMPT_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::{get, CosmosErrorKind};
+use warp::reject;
+
+/// Fails with `Fault::Throttling` while the subject is locked out.
+pub async fn check(subject: Subject<'_>) -> Result<(), warp::Rejection> {
+    let id = subject.id();
+    let attempts: SigninAttempts = match get(SIGNIN_ATTEMPT_COLLECTION, [&id], &id).await {
+        Ok((attempts, _)) => attempts,
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => return Ok(()),
+            _ => return Err(e.into()),
+        },
+    };
+    match attempts.locked_until {
+        Some(until) if until > Utc::now() => Err(reject::custom(Fault::Throttling)),
+        _ => Ok(()),
+    }
+}
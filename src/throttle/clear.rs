@@ -0,0 +1,15 @@
+use super::Subject;
+use crate::SIGNIN_ATTEMPT_COLLECTION;
+use cosmos_utils::{delete, CosmosErrorKind};
+
+/// Forgets the subject's failed attempts, e.g. after a successful signin.
+pub async fn clear(subject: Subject<'_>) -> Result<(), warp::Rejection> {
+    let id = subject.id();
+    match delete(SIGNIN_ATTEMPT_COLLECTION, [&id], &id, None).await {
+        Ok(_) => Ok(()),
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => Ok(()),
+            _ => Err(e.into()),
+        },
+    }
+}
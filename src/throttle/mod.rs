@@ -0,0 +1,78 @@
+use crate::util;
+use chrono::Duration;
+
+/// Length of the first lockout, every following lockout is twice as long.
+const FIRST_LOCKOUT_SECONDS: i64 = 60;
+
+/// Longest possible lockout.
+const MAX_LOCKOUT_HOURS: i64 = 24;
+
+/// Failures older than this no longer count towards a lockout.
+const FAILURE_WINDOW_HOURS: i64 = 1;
+
+/// What failed signin attempts are counted for.
+pub enum Subject<'a> {
+    Email(&'a str),
+    // Password reset emails requested for an email, every request counts. Kept apart from
+    // `Email` so that requesting resets can not lock the owner out of signing in.
+    PasswordReset(&'a str),
+    // Two factor codes entered by a signed in user, when enabling or disabling two factor
+    // authentication, so that a stolen session can not guess them.
+    TwoFactor(&'a str),
+    // Many users may share an ip, so an ip is allowed more failures than an email.
+    Ip(&'a str),
+    // Guests created from an ip, every guest counts. Kept apart from `Ip` so that creating
+    // guests can not lock the users of the ip out of signing in.
+    Guest(&'a str),
+    // Password reset emails requested from an ip, every request counts. Kept apart from `Ip` so
+    // that requesting resets can not lock the users of the ip out of signing in.
+    PasswordResetIp(&'a str),
+}
+
+impl Subject<'_> {
+    fn key(&self) -> String {
+        match self {
+            Subject::Email(email) => format!("email {}", email),
+            Subject::PasswordReset(email) => format!("password reset {}", email),
+            Subject::TwoFactor(user_id) => format!("two factor {}", user_id),
+            Subject::Ip(ip) => format!("ip {}", ip),
+            Subject::Guest(ip) => format!("guest {}", ip),
+            Subject::PasswordResetIp(ip) => format!("password reset ip {}", ip),
+        }
+    }
+
+    fn id(&self) -> String {
+        util::hash_token(&self.key())
+    }
+
+    fn max_failures(&self) -> u32 {
+        match self {
+            Subject::Email(_) => 5,
+            Subject::PasswordReset(_) => 5,
+            Subject::TwoFactor(_) => 5,
+            Subject::Ip(_) => 50,
+            Subject::Guest(_) => 50,
+            Subject::PasswordResetIp(_) => 50,
+        }
+    }
+}
+
+/// Length of lockout number `lockouts`, counting from zero.
+fn lockout_duration(lockouts: u32) -> Duration {
+    let max = Duration::hours(MAX_LOCKOUT_HOURS);
+    match 2i64.checked_pow(lockouts) {
+        Some(factor) if factor <= max.num_seconds() / FIRST_LOCKOUT_SECONDS => {
+            Duration::seconds(FIRST_LOCKOUT_SECONDS * factor)
+        }
+        _ => max,
+    }
+}
+
+mod check;
+pub use check::check;
+
+mod record_failure;
+pub use record_failure::record_failure;
+
+mod clear;
+pub use clear::clear;
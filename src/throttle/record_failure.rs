@@ -0,0 +1,67 @@
+use super::{lockout_duration, Subject, FAILURE_WINDOW_HOURS, MAX_LOCKOUT_HOURS};
+use crate::models::SigninAttempts;
+use crate::{APPLICATION_INSIGHTS_TELEMETRY_CLIENT, SIGNIN_ATTEMPT_COLLECTION};
+use appinsights::telemetry::SeverityLevel;
+use chrono::{Duration, Utc};
+use cosmos_utils::{insert, modify, CosmosErrorKind};
+
+/// Counts a failed signin attempt, locking the subject out once it has failed too many times.
+pub async fn record_failure(subject: Subject<'_>) -> Result<(), warp::Rejection> {
+    let id = subject.id();
+    let key = subject.key();
+    let max_failures = subject.max_failures();
+
+    let fail = |mut attempts: SigninAttempts| {
+        let now = Utc::now();
+        if attempts.modified + Duration::hours(FAILURE_WINDOW_HOURS) < now {
+            attempts.failures = 0;
+        }
+        // A subject that has behaved for a while starts over from the shortest lockout.
+        if let Some(until) = attempts.locked_until {
+            if until + Duration::hours(MAX_LOCKOUT_HOURS) < now {
+                attempts.lockouts = 0;
+            }
+        }
+        attempts.failures += 1;
+        if attempts.failures >= max_failures {
+            attempts.locked_until = Some(now + lockout_duration(attempts.lockouts));
+            attempts.lockouts += 1;
+            attempts.failures = 0;
+        }
+        attempts.modified = now;
+        attempts
+    };
+
+    let new = fail(SigninAttempts {
+        id: id.clone(),
+        key: key.clone(),
+        failures: 0,
+        lockouts: 0,
+        locked_until: None,
+        modified: Utc::now(),
+        created: Utc::now(),
+    });
+    let attempts = match insert(SIGNIN_ATTEMPT_COLLECTION, [&id], &new, None).await {
+        Ok(_) => new,
+        Err(e) => match e.kind {
+            CosmosErrorKind::Conflict => {
+                modify(SIGNIN_ATTEMPT_COLLECTION, [&id], &id, |a| Ok(fail(a))).await?
+            }
+            _ => return Err(e.into()),
+        },
+    };
+
+    // A lockout was just started.
+    if attempts.failures == 0 {
+        if let Some(until) = attempts.locked_until {
+            APPLICATION_INSIGHTS_TELEMETRY_CLIENT.track_trace(
+                format!(
+                    "Signin locked out for {} until {} (lockout {}).",
+                    key, until, attempts.lockouts
+                ),
+                SeverityLevel::Warning,
+            );
+        }
+    }
+    Ok(())
+}
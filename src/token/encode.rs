@@ -0,0 +1,150 @@
+use crate::fault::Fault;
+use crate::models::{Claims, Role, TokenPurpose, User};
+use crate::roles;
+use crate::token::{
+    TokenPair, ACCESS_TOKEN_LIFETIME_MINUTES, IMPERSONATION_TOKEN_LIFETIME_MINUTES, SIGNING_KEY,
+    VERIFICATION_KEYS,
+};
+use crate::{ACCESS_TOKEN_SECRET, HS256_ACCEPTED_UNTIL, REFRESH_TOKEN_SECRET};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{
+    decode, decode_header, encode, errors, errors::ErrorKind, Algorithm, DecodingKey, Header,
+    Validation,
+};
+use warp::reject;
+
+/// Signs the claims with the current signing key. Access, refresh and single purpose tokens share
+/// the key and are told apart by the token id, which only refresh tokens carry, and the purpose,
+/// which only single purpose tokens carry.
+fn sign(claims: &Claims) -> Result<String, warp::Rejection> {
+    let header = Header {
+        kid: Some(SIGNING_KEY.kid.clone()),
+        ..Header::new(SIGNING_KEY.alg)
+    };
+    encode(&header, claims, &SIGNING_KEY.key).map_err(|error| {
+        reject::custom(Fault::Unspecified(format!(
+            "Could not encode token: {}.",
+            error
+        )))
+    })
+}
+
+/// Verifies a token against the published keys, or against `legacy_secret` if the token is a
+/// HS256 token from before the switch to asymmetric keys.
+fn verify(token: &str, legacy_secret: Option<&str>) -> Result<Claims, errors::Error> {
+    let header = decode_header(token)?;
+    let token_data = if let (Algorithm::HS256, Some(legacy_secret)) = (header.alg, legacy_secret) {
+        if let Some(until) = *HS256_ACCEPTED_UNTIL {
+            if Utc::now() > until {
+                return Err(ErrorKind::InvalidAlgorithm.into());
+            }
+        }
+        decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(legacy_secret.as_ref()),
+            &Validation::default(),
+        )?
+    } else {
+        let key = VERIFICATION_KEYS
+            .iter()
+            .find(|k| header.kid.as_ref() == Some(&k.kid) && k.alg == header.alg)
+            .ok_or_else(|| errors::Error::from(ErrorKind::InvalidSignature))?;
+        decode::<Claims>(token, &key.key, &Validation::new(key.alg))?
+    };
+    Ok(token_data.claims)
+}
+
+/// Encodes the token pair of a session. Both tokens carry the session (refresh token family) id
+/// and the user's token and role generations, only the refresh token carries a token id.
+pub fn encode_token_pair(
+    user: &User,
+    roles: &Vec<Role>,
+    family_id: &str,
+    token_id: &str,
+    refresh_exp: DateTime<Utc>,
+) -> Result<TokenPair, warp::Rejection> {
+    let access_exp = Utc::now() + Duration::minutes(ACCESS_TOKEN_LIFETIME_MINUTES);
+    let access_token = sign(&Claims {
+        fam: Some(family_id.to_string()),
+        gen: user.token_generation,
+        rgn: user.role_generation,
+        ..Claims::new(&user.id, access_exp, roles)
+    })?;
+    let refresh_token = sign(&Claims {
+        jti: Some(token_id.to_string()),
+        fam: Some(family_id.to_string()),
+        gen: user.token_generation,
+        rgn: user.role_generation,
+        ..Claims::new(&user.id, refresh_exp, roles)
+    })?;
+
+    Ok(TokenPair {
+        access_token,
+        refresh_token,
+    })
+}
+
+/// Encodes an access token letting the actor act as the user, read-only unless `write`. The token
+/// has no session and can not be refreshed, it carries the user's generations so that it is
+/// revoked along with the user's own tokens. Of the user's roles it only carries the unmanaged
+/// ones, so that the actor gains none of the user's admin powers.
+pub fn encode_impersonation_token(
+    user: &User,
+    actor_id: &str,
+    write: bool,
+) -> Result<(String, DateTime<Utc>), warp::Rejection> {
+    let exp = Utc::now() + Duration::minutes(IMPERSONATION_TOKEN_LIFETIME_MINUTES);
+    let claims = Claims {
+        gen: user.token_generation,
+        rgn: user.role_generation,
+        act: Some(actor_id.to_string()),
+        wrt: write,
+        ..Claims::new(&user.id, exp, &roles::unmanaged(&user.roles))
+    };
+    Ok((sign(&claims)?, claims.exp))
+}
+
+/// Decodes and validates an access token, rejecting refresh tokens.
+pub fn decode_access_token(token: &str) -> Result<Claims, warp::Rejection> {
+    match verify(token, Some(&ACCESS_TOKEN_SECRET)) {
+        Ok(claims) if claims.jti.is_none() && claims.pur.is_none() => Ok(claims),
+        _ => Err(reject::custom(Fault::Unauthorized)),
+    }
+}
+
+/// Decodes and validates a refresh token, including its expiry.
+pub fn decode_refresh_token(token: &str) -> Result<Claims, warp::Rejection> {
+    match verify(token, Some(&REFRESH_TOKEN_SECRET)) {
+        Ok(c) if c.pur.is_none() => Ok(c),
+        Ok(_) => Err(reject::custom(Fault::Unauthorized)),
+        Err(error) => match error.kind() {
+            ErrorKind::ExpiredSignature => Err(reject::custom(Fault::Unauthorized)),
+            _ => Err(reject::custom(Fault::IllegalArgument(format!(
+                "Could not decode token: {}.",
+                error
+            )))),
+        },
+    }
+}
+
+/// Encodes a single purpose token for `email`, e.g. for a link sent to that address.
+pub fn encode_purpose_token(
+    user_id: &str,
+    purpose: TokenPurpose,
+    email: &str,
+    exp: DateTime<Utc>,
+) -> Result<String, warp::Rejection> {
+    sign(&Claims {
+        pur: Some(purpose),
+        eml: Some(email.to_string()),
+        ..Claims::new(user_id, exp, &vec![])
+    })
+}
+
+/// Decodes and validates a single purpose token, rejecting tokens issued for other purposes.
+pub fn decode_purpose_token(token: &str, purpose: TokenPurpose) -> Result<Claims, warp::Rejection> {
+    match verify(token, None) {
+        Ok(claims) if claims.pur == Some(purpose) => Ok(claims),
+        _ => Err(reject::custom(Fault::Unauthorized)),
+    }
+}
@@ -0,0 +1,37 @@
+use crate::models::{RefreshTokenFamily, User};
+use crate::token::{encode_token_pair, refresh_token_lifetime, TokenPair};
+use crate::two_factor;
+use crate::util;
+use crate::REFRESH_TOKEN_COLLECTION;
+use chrono::Utc;
+use cosmos_utils::insert;
+
+/// Starts a new refresh token family for the user and returns its first token pair. Used
+/// whenever a user authenticates from scratch, e.g. on signin and signup. `two_factor` tells
+/// whether the user authenticated with a second factor, see `two_factor::session_roles`, and `ip`
+/// is the client ip the session is registered with.
+pub async fn issue(user: &User, two_factor: bool, ip: &str) -> Result<TokenPair, warp::Rejection> {
+    let user_id = &user.id;
+    let roles = two_factor::session_roles(&user.roles, two_factor).await?;
+    let now = Utc::now();
+    let exp = now + refresh_token_lifetime(user);
+    let family = RefreshTokenFamily {
+        id: util::new_guid_v4(),
+        user_id: user_id.to_string(),
+        current: util::new_guid_v4(),
+        revoked: false,
+        two_factor,
+        device: None,
+        ip: Some(ip.to_string()),
+        refreshed: None,
+        expires: exp,
+        modified: now,
+        created: now,
+    };
+
+    let tokens = encode_token_pair(user, &roles, &family.id, &family.current, exp)?;
+
+    insert(REFRESH_TOKEN_COLLECTION, [user_id], &family, None).await?;
+
+    Ok(tokens)
+}
@@ -0,0 +1,171 @@
+use crate::{TOKEN_SIGNING_KEYS, TOKEN_SIGNING_KID};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use simple_asn1::{from_der, ASN1Block};
+
+/// A key as configured in `TOKEN_SIGNING_KEYS`, which holds a JSON array of these. Keys without a
+/// private key are only used for verification, which allows a new key to be published before it
+/// is used for signing and an old key to keep verifying tokens after it is retired.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyConfig {
+    kid: String,
+
+    // ES256 or RS256.
+    alg: Algorithm,
+
+    // PEM encoded SubjectPublicKeyInfo ("BEGIN PUBLIC KEY").
+    public_key: String,
+
+    // PEM encoded PKCS#8 private key, only required for the signing key.
+    #[serde(default)]
+    private_key: Option<String>,
+}
+
+/// Public key in JSON Web Key format (RFC 7517), as published in the JWKS.
+#[derive(Serialize, Debug, Clone)]
+pub struct Jwk {
+    pub kty: String,
+
+    pub kid: String,
+
+    pub alg: Algorithm,
+
+    #[serde(rename = "use")]
+    pub usage: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+}
+
+pub struct VerificationKey {
+    pub kid: String,
+    pub alg: Algorithm,
+    pub key: DecodingKey<'static>,
+    pub jwk: Jwk,
+}
+
+pub struct SigningKey {
+    pub kid: String,
+    pub alg: Algorithm,
+    pub key: EncodingKey,
+}
+
+lazy_static! {
+    static ref KEY_CONFIGS: Vec<KeyConfig> = serde_json::from_str(&TOKEN_SIGNING_KEYS).unwrap();
+    pub static ref VERIFICATION_KEYS: Vec<VerificationKey> = KEY_CONFIGS
+        .iter()
+        .map(|c| verification_key(c).unwrap())
+        .collect();
+    pub static ref SIGNING_KEY: SigningKey = {
+        let config = KEY_CONFIGS
+            .iter()
+            .find(|c| c.kid == *TOKEN_SIGNING_KID)
+            .unwrap();
+        let private_key = config.private_key.as_ref().unwrap().as_bytes();
+        let key = match config.alg {
+            Algorithm::ES256 => EncodingKey::from_ec_pem(private_key).unwrap(),
+            Algorithm::RS256 => EncodingKey::from_rsa_pem(private_key).unwrap(),
+            alg => panic!("Unsupported token signing algorithm {:?}.", alg),
+        };
+        SigningKey {
+            kid: config.kid.clone(),
+            alg: config.alg,
+            key,
+        }
+    };
+}
+
+fn encode_component(bytes: &[u8]) -> String {
+    base64::encode_config(bytes, base64::URL_SAFE_NO_PAD)
+}
+
+/// Parses the public key of a key config and extracts the components needed for its JWK.
+fn verification_key(config: &KeyConfig) -> Result<VerificationKey, String> {
+    let pem = pem::parse(&config.public_key).map_err(|e| format!("{} ({})", config.kid, e))?;
+
+    // SubjectPublicKeyInfo ::= SEQUENCE { algorithm AlgorithmIdentifier, subjectPublicKey BIT STRING }
+    let public_key = match from_der(&pem.contents).as_deref() {
+        Ok([ASN1Block::Sequence(_, info)]) => match info.as_slice() {
+            [_, ASN1Block::BitString(_, _, key)] => key.clone(),
+            _ => return Err(format!("{} is not a SubjectPublicKeyInfo", config.kid)),
+        },
+        _ => return Err(format!("{} is not a SubjectPublicKeyInfo", config.kid)),
+    };
+
+    let (key, jwk) = match config.alg {
+        Algorithm::ES256 => {
+            // Uncompressed P-256 point, 0x04 followed by the x and y coordinates.
+            if public_key.len() != 65 || public_key[0] != 0x04 {
+                return Err(format!("{} is not an uncompressed P-256 key", config.kid));
+            }
+            let key = DecodingKey::from_ec_pem(config.public_key.as_bytes())
+                .map_err(|e| format!("{} ({})", config.kid, e))?
+                .into_static();
+            let jwk = Jwk {
+                kty: String::from("EC"),
+                kid: config.kid.clone(),
+                alg: config.alg,
+                usage: String::from("sig"),
+                n: None,
+                e: None,
+                crv: Some(String::from("P-256")),
+                x: Some(encode_component(&public_key[1..33])),
+                y: Some(encode_component(&public_key[33..])),
+            };
+            (key, jwk)
+        }
+        Algorithm::RS256 => {
+            // RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+            let (n, e) = match from_der(&public_key).as_deref() {
+                Ok([ASN1Block::Sequence(_, parts)]) => match parts.as_slice() {
+                    [ASN1Block::Integer(_, n), ASN1Block::Integer(_, e)] => (
+                        encode_component(&n.to_bytes_be().1),
+                        encode_component(&e.to_bytes_be().1),
+                    ),
+                    _ => return Err(format!("{} is not an RSA public key", config.kid)),
+                },
+                _ => return Err(format!("{} is not an RSA public key", config.kid)),
+            };
+            let key = DecodingKey::from_rsa_components(&n, &e).into_static();
+            let jwk = Jwk {
+                kty: String::from("RSA"),
+                kid: config.kid.clone(),
+                alg: config.alg,
+                usage: String::from("sig"),
+                n: Some(n),
+                e: Some(e),
+                crv: None,
+                x: None,
+                y: None,
+            };
+            (key, jwk)
+        }
+        alg => {
+            return Err(format!(
+                "{} uses unsupported algorithm {:?}",
+                config.kid, alg
+            ))
+        }
+    };
+
+    Ok(VerificationKey {
+        kid: config.kid.clone(),
+        alg: config.alg,
+        key,
+        jwk,
+    })
+}
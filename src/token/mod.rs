@@ -0,0 +1,57 @@
+use crate::models::User;
+use chrono::Duration;
+use serde::Serialize;
+
+/// Lifetime of an access token in minutes.
+const ACCESS_TOKEN_LIFETIME_MINUTES: i64 = 20;
+
+/// Lifetime of an impersonation token in minutes, impersonation tokens can not be refreshed.
+const IMPERSONATION_TOKEN_LIFETIME_MINUTES: i64 = 15;
+
+/// Lifetime of a refresh token in days, every rotation issues a token with a fresh lifetime.
+const REFRESH_TOKEN_LIFETIME_DAYS: i64 = 30;
+
+/// Lifetime of a guest's refresh token in days, a guest that stays away has to start over.
+const GUEST_REFRESH_TOKEN_LIFETIME_DAYS: i64 = 7;
+
+fn refresh_token_lifetime(user: &User) -> Duration {
+    if user.guest {
+        Duration::days(GUEST_REFRESH_TOKEN_LIFETIME_DAYS)
+    } else {
+        Duration::days(REFRESH_TOKEN_LIFETIME_DAYS)
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+mod keys;
+pub use keys::{Jwk, SIGNING_KEY, VERIFICATION_KEYS};
+
+mod encode;
+pub use encode::{
+    decode_access_token, decode_purpose_token, decode_refresh_token, encode_impersonation_token,
+    encode_purpose_token, encode_token_pair,
+};
+
+mod issue;
+pub use issue::issue;
+
+mod rotate;
+pub use rotate::rotate;
+
+mod revoke;
+pub use revoke::{revoke, revoke_all};
+
+mod verify_generation;
+pub use verify_generation::verify_generation;
+
+mod verify_impersonation;
+pub use verify_impersonation::verify_impersonation;
+
+mod verify_access_token;
+pub use verify_access_token::verify_access_token;
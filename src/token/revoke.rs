@@ -0,0 +1,64 @@
+use crate::models::{RefreshTokenFamily, User};
+use crate::util::log;
+use crate::{REFRESH_TOKEN_COLLECTION, USER_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{modify, query_crosspartition_etag, upsert};
+
+/// Revokes a single refresh token family. The session can no longer be refreshed, its current
+/// access token stays valid until it expires. The push handle of the session's device is
+/// unregistered from the user.
+pub async fn revoke(user_id: &str, family_id: &str) -> Result<(), warp::Rejection> {
+    let family = modify(
+        REFRESH_TOKEN_COLLECTION,
+        [user_id],
+        family_id,
+        |mut family: RefreshTokenFamily| {
+            family.revoked = true;
+            family.modified = Utc::now();
+            Ok(family)
+        },
+    )
+    .await?;
+
+    if let Some(device) = family.device {
+        modify(USER_COLLECTION, [user_id], user_id, |mut user: User| {
+            user.devices.retain(|d| d.handle != device.handle);
+            user.modified = Utc::now();
+            Ok(user)
+        })
+        .await?;
+    }
+    Ok(())
+}
+
+/// Invalidates every access and refresh token issued to the user by bumping the user's token
+/// generation, and revokes all of the user's refresh token families. Returns the updated user.
+pub async fn revoke_all(user_id: &str) -> Result<User, warp::Rejection> {
+    let user = modify(USER_COLLECTION, [user_id], user_id, |mut user: User| {
+        user.token_generation += 1;
+        user.modified = Utc::now();
+        Ok(user)
+    })
+    .await?;
+
+    // NOTE: The generation bump alone is enough to reject the tokens, marking the families as
+    // revoked is best effort.
+    let q = format!(
+        "SELECT * FROM {} f WHERE NOT IS_DEFINED(f.revoked)",
+        REFRESH_TOKEN_COLLECTION
+    );
+    let families: Vec<(RefreshTokenFamily, _)> =
+        query_crosspartition_etag(REFRESH_TOKEN_COLLECTION, [user_id], q, -1, false).await?;
+    let now = Utc::now();
+    for (mut family, etag) in families {
+        family.revoked = true;
+        family.modified = now;
+        if let Err(e) = upsert(REFRESH_TOKEN_COLLECTION, [user_id], &family, Some(&etag)).await {
+            log(format!(
+                "Could not revoke refresh token family {} due to {}",
+                family.id, e
+            ));
+        }
+    }
+    Ok(user)
+}
@@ -0,0 +1,88 @@
+use crate::fault::Fault;
+use crate::models::{RefreshTokenFamily, User};
+use crate::token::{decode_refresh_token, encode_token_pair, refresh_token_lifetime, TokenPair};
+use crate::two_factor;
+use crate::util::{self, log};
+use crate::{REFRESH_TOKEN_COLLECTION, USER_COLLECTION};
+use chrono::Utc;
+use cosmos_utils::{get, modify};
+use std::sync::atomic::{AtomicBool, Ordering};
+use warp::reject;
+
+/// Exchanges a refresh token for a new token pair. The presented refresh token is invalidated,
+/// and if it had already been exchanged before the whole family is revoked since the token has
+/// most likely leaked. `ip` is recorded as the session's latest client ip.
+pub async fn rotate(
+    user_id: &str,
+    refresh_token: &str,
+    ip: &str,
+) -> Result<TokenPair, warp::Rejection> {
+    let claims = decode_refresh_token(refresh_token)?;
+    if user_id != claims.sub {
+        return Err(reject::custom(Fault::IllegalArgument(format!(
+            "User id in url does not match token ({} != {}).",
+            user_id, claims.sub
+        ))));
+    }
+
+    // Tokens issued before families were introduced can not be rotated.
+    let (jti, fam) = match (claims.jti, claims.fam) {
+        (Some(jti), Some(fam)) => (jti, fam),
+        _ => return Err(reject::custom(Fault::Unauthorized)),
+    };
+
+    let (user, _etag): (User, _) = get(USER_COLLECTION, [user_id], user_id).await?;
+    if user.deleted {
+        return Err(reject::custom(Fault::Forbidden(String::from(
+            "User is deleted.",
+        ))));
+    }
+
+    // The user has signed out everywhere or changed password since the token was issued.
+    if user.token_generation != claims.gen {
+        return Err(reject::custom(Fault::Unauthorized));
+    }
+
+    let now = Utc::now();
+    let exp = now + refresh_token_lifetime(&user);
+    let next = util::new_guid_v4();
+    let reused = AtomicBool::new(false);
+
+    // NOTE: The reuse check is done inside the transform so that two concurrent exchanges of the
+    // same token can not both succeed.
+    let family = modify(
+        REFRESH_TOKEN_COLLECTION,
+        [user_id],
+        &fam,
+        |mut family: RefreshTokenFamily| {
+            reused.store(false, Ordering::Relaxed);
+            if !family.revoked {
+                if family.current == jti {
+                    family.current = next.clone();
+                    family.expires = exp;
+                    family.ip = Some(ip.to_string());
+                    family.refreshed = Some(now);
+                } else {
+                    family.revoked = true;
+                    reused.store(true, Ordering::Relaxed);
+                }
+                family.modified = now;
+            }
+            Ok(family)
+        },
+    )
+    .await?;
+
+    if family.revoked {
+        if reused.load(Ordering::Relaxed) {
+            log(format!(
+                "Refresh token reuse detected, revoked family {} of user {}.",
+                family.id, user_id
+            ));
+        }
+        return Err(reject::custom(Fault::Unauthorized));
+    }
+
+    let roles = two_factor::session_roles(&user.roles, family.two_factor).await?;
+    encode_token_pair(&user, &roles, &fam, &next, exp)
+}
@@ -0,0 +1,16 @@
+use super::{decode_access_token, verify_generation, verify_impersonation};
+use crate::models::Claims;
+use warp::http::Method;
+
+/// Decodes the access token sent with a request and runs every check an access token has to pass,
+/// shared by all filters that accept one.
+pub async fn verify_access_token(
+    token: &str,
+    method: &Method,
+    path: &str,
+    request_id: &str,
+) -> Result<Claims, warp::Rejection> {
+    let claims = decode_access_token(token)?;
+    let claims = verify_generation(claims).await?;
+    verify_impersonation(claims, method, path, request_id).await
+}
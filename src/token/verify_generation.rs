@@ -0,0 +1,23 @@
+use crate::fault::Fault;
+use crate::models::{Claims, User};
+use crate::USER_COLLECTION;
+use cosmos_utils::{get, CosmosErrorKind};
+use warp::reject;
+
+/// Checks that the claims belong to the user's current token generation, i.e. that the user has
+/// neither signed out everywhere, changed password nor been deleted since the token was issued.
+/// Claims of an earlier role generation are rejected as well, since their roles may be stale.
+pub async fn verify_generation(claims: Claims) -> Result<Claims, warp::Rejection> {
+    let (user, _etag): (User, _) = match get(USER_COLLECTION, [&claims.sub], &claims.sub).await {
+        Ok(u) => u,
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => return Err(reject::custom(Fault::Unauthorized)),
+            _ => return Err(e.into()),
+        },
+    };
+
+    if user.deleted || user.token_generation != claims.gen || user.role_generation != claims.rgn {
+        return Err(reject::custom(Fault::Unauthorized));
+    }
+    Ok(claims)
+}
@@ -0,0 +1,26 @@
+use crate::audit;
+use crate::fault::Fault;
+use crate::models::Claims;
+use warp::http::Method;
+use warp::reject;
+
+/// Records a request made with an impersonation token in the audit log, including requests that
+/// are then refused, and checks that the token is only used for reading unless it was issued for
+/// writing. Other tokens pass unchanged.
+pub async fn verify_impersonation(
+    claims: Claims,
+    method: &Method,
+    path: &str,
+    request_id: &str,
+) -> Result<Claims, warp::Rejection> {
+    if claims.act.is_none() {
+        return Ok(claims);
+    }
+    audit::record_request(request_id, &claims, method, path).await;
+    if !claims.wrt && method != Method::GET && method != Method::HEAD {
+        return Err(reject::custom(Fault::Forbidden(String::from(
+            "Impersonation token is read-only.",
+        ))));
+    }
+    Ok(claims)
+}
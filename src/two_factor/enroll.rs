@@ -0,0 +1,75 @@
+use super::totp::base32;
+use super::{verify, DIGITS, ENCRYPTION_KEY, ISSUER, RECOVERY_CODE_COUNT, STEP_SECONDS};
+use crate::fault::Fault;
+use crate::models::{AuthEmail, TwoFactor};
+use crate::util;
+use rand::Rng;
+use serde::Serialize;
+use url::form_urlencoded::byte_serialize;
+use warp::reject;
+
+/// What the user needs to set up an authenticator app.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Enrollment {
+    // Base32 secret for entering by hand.
+    pub secret: String,
+
+    // otpauth:// uri, usually shown as a QR code.
+    pub provisioning_uri: String,
+}
+
+/// Generates a new secret for the auth email. Two factor authentication is not enabled until the
+/// secret is confirmed.
+pub fn enroll(auth_email: &mut AuthEmail) -> Result<Enrollment, warp::Rejection> {
+    if let Some(two_factor) = &auth_email.two_factor {
+        if two_factor.confirmed {
+            return Err(reject::custom(Fault::IllegalState(String::from(
+                "Two factor authentication is already enabled",
+            ))));
+        }
+    }
+
+    let secret: [u8; 20] = rand::thread_rng().gen();
+    auth_email.two_factor = Some(TwoFactor {
+        secret: util::encrypt_string(base64::encode(secret), &ENCRYPTION_KEY)?,
+        confirmed: false,
+        recovery_codes: vec![],
+        last_step: 0,
+    });
+
+    let secret = base32(&secret);
+    let issuer: String = byte_serialize(ISSUER.as_bytes()).collect();
+    let account: String = byte_serialize(auth_email.id.as_bytes()).collect();
+    let provisioning_uri = format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        issuer, account, secret, issuer, DIGITS, STEP_SECONDS
+    );
+    Ok(Enrollment {
+        secret,
+        provisioning_uri,
+    })
+}
+
+/// Enables two factor authentication given a code from the newly set up authenticator. Returns
+/// the recovery codes, which are only stored hashed and can not be shown again.
+pub fn confirm(auth_email: &mut AuthEmail, code: &str) -> Result<Vec<String>, warp::Rejection> {
+    let two_factor = match &mut auth_email.two_factor {
+        Some(two_factor) if !two_factor.confirmed => two_factor,
+        _ => {
+            return Err(reject::custom(Fault::IllegalState(String::from(
+                "Two factor authentication is not being set up",
+            ))))
+        }
+    };
+    if !verify(two_factor, code)? {
+        return Err(reject::custom(Fault::WrongPassword));
+    }
+
+    let recovery_codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+        .map(|_| util::random_string(10))
+        .collect();
+    two_factor.recovery_codes = recovery_codes.iter().map(|c| util::hash_token(c)).collect();
+    two_factor.confirmed = true;
+    Ok(recovery_codes)
+}
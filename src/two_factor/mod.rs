@@ -0,0 +1,62 @@
+use crate::models::RoleFlags;
+use crate::util::SecretKey;
+use crate::TWO_FACTOR_ENCRYPTION_KEY;
+use lazy_static::lazy_static;
+
+/// Length of a TOTP time step in seconds.
+const STEP_SECONDS: u64 = 30;
+
+/// Number of digits in a TOTP code.
+const DIGITS: u32 = 6;
+
+/// Codes from this many steps before or after the current one are accepted, to allow for clock
+/// drift.
+const ALLOWED_DRIFT_STEPS: u64 = 1;
+
+/// Number of recovery codes handed out when two factor authentication is enabled.
+const RECOVERY_CODE_COUNT: usize = 10;
+
+/// How long a signin challenge can be answered.
+const CHALLENGE_LIFETIME_MINUTES: i64 = 5;
+
+/// Name of the service shown in authenticator apps.
+const ISSUER: &str = "Prime Crime";
+
+/// Role flags that may require a second factor.
+const ADMIN_FLAGS: RoleFlags = RoleFlags::from_bits_truncate(
+    RoleFlags::OFFICE_CONTENT_ADMIN.bits()
+        | RoleFlags::OFFICE_BILLING_ADMIN.bits()
+        | RoleFlags::OFFICE_PERSONNEL_ADMIN.bits()
+        | RoleFlags::GLOBAL_CONTENT_ADMIN.bits()
+        | RoleFlags::GLOBAL_BILLING_ADMIN.bits()
+        | RoleFlags::GLOBAL_PERSONNEL_ADMIN.bits(),
+);
+
+/// Role flags that are not tied to an office.
+const GLOBAL_FLAGS: RoleFlags = RoleFlags::from_bits_truncate(
+    RoleFlags::GLOBAL_CONTENT_ADMIN.bits()
+        | RoleFlags::GLOBAL_BILLING_ADMIN.bits()
+        | RoleFlags::GLOBAL_PERSONNEL_ADMIN.bits(),
+);
+
+lazy_static! {
+    static ref ENCRYPTION_KEY: SecretKey = SecretKey::from_slice(
+        &base64::decode(&*TWO_FACTOR_ENCRYPTION_KEY)
+            .expect("TWO_FACTOR_ENCRYPTION_KEY is not base64")
+    )
+    .expect("TWO_FACTOR_ENCRYPTION_KEY is not a valid key");
+}
+
+mod totp;
+
+mod enroll;
+pub use enroll::{confirm, enroll};
+
+mod verify;
+pub use verify::verify;
+
+mod signin;
+pub use signin::{begin_signin, Signin};
+
+mod session_roles;
+pub use session_roles::session_roles;
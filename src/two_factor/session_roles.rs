@@ -0,0 +1,51 @@
+use super::{ADMIN_FLAGS, GLOBAL_FLAGS};
+use crate::models::{Office, Role};
+use crate::{OFFICE_COLLECTION, REQUIRE_TWO_FACTOR_FOR_ADMINS};
+use cosmos_utils::{get, CosmosErrorKind};
+
+/// The roles a session may use. Unless the session was started with a second factor, admin flags
+/// are dropped from the roles that the global policy or the role's office requires a second
+/// factor for.
+pub async fn session_roles(roles: &[Role], two_factor: bool) -> Result<Vec<Role>, warp::Rejection> {
+    if two_factor {
+        return Ok(roles.to_vec());
+    }
+    let mut session_roles = vec![];
+    for role in roles {
+        if !role.flg.intersects(ADMIN_FLAGS) || !requires_two_factor(role).await? {
+            session_roles.push(role.clone());
+            continue;
+        }
+        let flg = role.flg - ADMIN_FLAGS;
+        if !flg.is_empty() {
+            session_roles.push(Role {
+                flg,
+                sub: role.sub.clone(),
+            });
+        }
+    }
+    Ok(session_roles)
+}
+
+async fn requires_two_factor(role: &Role) -> Result<bool, warp::Rejection> {
+    if *REQUIRE_TWO_FACTOR_FOR_ADMINS {
+        return Ok(true);
+    }
+    if role.flg.intersects(GLOBAL_FLAGS) {
+        return Ok(false);
+    }
+    let office_id = match &role.sub {
+        Some(office_id) => office_id,
+        None => return Ok(false),
+    };
+    match get(OFFICE_COLLECTION, [office_id], office_id).await {
+        Ok((office, _)) => {
+            let office: Office = office;
+            Ok(office.require_two_factor)
+        }
+        Err(e) => match e.kind {
+            CosmosErrorKind::NotFound => Ok(false),
+            _ => Err(e.into()),
+        },
+    }
+}
@@ -0,0 +1,36 @@
+use super::CHALLENGE_LIFETIME_MINUTES;
+use crate::models::{AuthEmail, TokenPurpose, User};
+use crate::token::{self, TokenPair};
+use chrono::{Duration, Utc};
+
+/// Outcome of a signin with the first factor.
+pub enum Signin {
+    Session(TokenPair),
+    // Token to answer with a code from the second factor.
+    Challenge(String),
+}
+
+/// Starts a session for a user who has signed in with the first factor, or returns a challenge if
+/// the user has two factor authentication enabled.
+pub async fn begin_signin(
+    user: &User,
+    auth_email: Option<&AuthEmail>,
+    ip: &str,
+) -> Result<Signin, warp::Rejection> {
+    if let Some(auth_email) = auth_email {
+        if let Some(two_factor) = &auth_email.two_factor {
+            if two_factor.confirmed {
+                let exp = Utc::now() + Duration::minutes(CHALLENGE_LIFETIME_MINUTES);
+                let challenge = token::encode_purpose_token(
+                    &user.id,
+                    TokenPurpose::TwoFactor,
+                    &auth_email.id,
+                    exp,
+                )?;
+                return Ok(Signin::Challenge(challenge));
+            }
+        }
+    }
+    let tokens = token::issue(user, false, ip).await?;
+    Ok(Signin::Session(tokens))
+}
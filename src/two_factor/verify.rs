@@ -0,0 +1,60 @@
+use super::totp::{code_at, current_step};
+use super::{ALLOWED_DRIFT_STEPS, DIGITS, ENCRYPTION_KEY};
+use crate::fault::Fault;
+use crate::models::TwoFactor;
+use crate::util;
+use warp::reject;
+
+/// Checks a TOTP code, or one of the recovery codes, against the second factor. An accepted code
+/// is used up, so the second factor must be stored afterwards. Returns whether the code was
+/// accepted.
+pub fn verify(two_factor: &mut TwoFactor, code: &str) -> Result<bool, warp::Rejection> {
+    let code = code.trim();
+    if code.len() == DIGITS as usize && code.chars().all(|c| c.is_ascii_digit()) {
+        let secret = base64::decode(util::decrypt_string(&two_factor.secret, &ENCRYPTION_KEY)?)
+            .map_err(|_| {
+                reject::custom(Fault::Unspecified(String::from(
+                    "Could not decode two factor secret",
+                )))
+            })?;
+        let code: u32 = code.parse().unwrap_or_default();
+        let current = current_step();
+        for step in current.saturating_sub(ALLOWED_DRIFT_STEPS)..=current + ALLOWED_DRIFT_STEPS {
+            if step > two_factor.last_step && code_at(&secret, step) == code {
+                two_factor.last_step = step;
+                return Ok(true);
+            }
+        }
+        return Ok(false);
+    }
+
+    let hash = util::hash_token(code);
+    match two_factor.recovery_codes.iter().position(|c| *c == hash) {
+        Some(i) => {
+            two_factor.recovery_codes.remove(i);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::verify;
+    use crate::models::TwoFactor;
+    use crate::util;
+
+    #[test]
+    fn recovery_codes_are_single_use() {
+        let mut two_factor = TwoFactor {
+            secret: String::new(),
+            confirmed: true,
+            recovery_codes: vec![util::hash_token("abcdefghij")],
+            last_step: 0,
+        };
+        assert!(!verify(&mut two_factor, "jihgfedcba").unwrap());
+        assert!(verify(&mut two_factor, " abcdefghij ").unwrap());
+        assert!(!verify(&mut two_factor, "abcdefghij").unwrap());
+        assert!(two_factor.recovery_codes.is_empty());
+    }
+}
@@ -1,14 +1,15 @@
 use crate::{
     fault::Fault,
-    models::{Claims, RoleFlags},
+    models::Claims,
+    policy::{self, Action, Resource},
     APPLICATION_INSIGHTS_TELEMETRY_CLIENT,
 };
 use appinsights::telemetry::SeverityLevel;
-use argon2::{self, Config};
 use base64::encode;
 pub use orion::aead::{seal, SecretKey};
 use rand::{distributions::Distribution, seq::SliceRandom, thread_rng, Rng};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use warp::reject;
 
 pub fn log<S: Into<String>>(msg: S) {
@@ -89,7 +90,6 @@ impl Distribution<char> for Symbols {
     }
 }
 
-#[allow(dead_code)]
 pub fn random_string(n: usize) -> String {
     thread_rng().sample_iter(&Symbols).take(n).collect()
 }
@@ -103,20 +103,14 @@ impl Distribution<char> for Digits {
     }
 }
 
-//pub fn random_digit_string(n: usize) -> String {
-//    thread_rng().sample_iter(&Digits).take(n).collect()
-//}
-
-// Hash string using Argon2.
-pub fn hash(text: &[u8]) -> String {
-    let salt = rand::thread_rng().gen::<[u8; 32]>();
-    let config = Config::default();
-    argon2::hash_encoded(text, &salt, &config).unwrap()
+pub fn random_digit_string(n: usize) -> String {
+    thread_rng().sample_iter(&Digits).take(n).collect()
 }
 
-// Verify hash using Argon2.
-pub fn verify_hash(hash: &str, password: &[u8]) -> bool {
-    argon2::verify_encoded(hash, password).unwrap_or(false)
+// Hash a random token using SHA-256. Tokens are stored by their hash so that they can be looked
+// up but not used by anyone who can read the database.
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
 }
 
 ///// Returns the partition key and the specific id split up
@@ -189,31 +183,17 @@ pub fn verify_hash(hash: &str, password: &[u8]) -> bool {
 //    return false;
 //}
 
-/// If sub is `Some` and claims has one of the provided flags for the given subject then returns
-/// true. If sub is `None` and claims has one of the provided flags then return true. Else return
-/// false.
-pub fn has_role(sub: Option<&str>, claims: &Claims, valid_flags: RoleFlags) -> bool {
-    for e in &claims.rol {
-        // Does the current role intersect with the required roles.
-        if !e.flg.intersects(valid_flags) {
-            continue;
-        }
-
-        if let Some(g) = sub {
-            if let Some(s) = &e.sub {
-                if s.starts_with(g) {
-                    return true;
-                }
-            }
-        } else {
-            // No subject provided, so a match on flags suffices.
-            return true;
-        }
-    }
-    return false;
+/// Whether the claims are granted the action on the subject, which is the resource's owner or
+/// office depending on the action's grants. Called by the handlers `third_pact::model` generates,
+/// other handlers use `policy::authorize`.
+pub fn has_role(sub: Option<&str>, claims: &Claims, action: Action) -> bool {
+    let resource = Resource {
+        owner: sub,
+        offices: sub.into_iter().collect(),
+    };
+    policy::allows(claims, action, &resource)
 }
 
-#[allow(dead_code)]
 pub fn decrypt_string(
     encrypted_string: &str,
     password: &SecretKey,